@@ -0,0 +1,70 @@
+//! The `interval!` macro for [`sqlx_postgres_interval`], which parses an
+//! interval literal at compile time.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use sqlx_postgres_interval::Interval;
+use syn::{LitStr, parse_macro_input};
+
+/// An [`Interval`] parsed from a string literal at compile time, with the
+/// same parser as `Interval`'s `FromStr`: `interval!("1 year 2 mons 3 days")`,
+/// `interval!("P1Y2M3D")`, `interval!("01:30:00")`, `interval!("infinity")`.
+///
+/// It expands to a constant, so it can initialize `const` and `static` items:
+///
+/// ```
+/// use sqlx_postgres_interval::Interval;
+/// use sqlx_postgres_interval_macros::interval;
+///
+/// const TIMEOUT: Interval = interval!("30 minutes");
+/// assert!(TIMEOUT.is_identical(&Interval::new(0, 0, 1_800_000_000)));
+/// ```
+///
+/// Input the server would reject is a compile error at the literal, whether
+/// it is invalid:
+///
+/// ```compile_fail
+/// # use sqlx_postgres_interval_macros::interval;
+/// let twice = interval!("1 week 1 week");
+/// ```
+///
+/// ```compile_fail
+/// # use sqlx_postgres_interval_macros::interval;
+/// let empty = interval!("");
+/// ```
+///
+/// or out of range:
+///
+/// ```compile_fail
+/// # use sqlx_postgres_interval_macros::interval;
+/// let too_long = interval!("2147483648 years");
+/// ```
+///
+/// The argument must be a string literal:
+///
+/// ```compile_fail
+/// # use sqlx_postgres_interval_macros::interval;
+/// let days = interval!(30);
+/// ```
+#[proc_macro]
+pub fn interval(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let Interval {
+        months,
+        days,
+        microseconds,
+    } = match literal.value().parse::<Interval>() {
+        Ok(interval) => interval,
+        Err(e) => {
+            return syn::Error::new(literal.span(), e).to_compile_error().into();
+        }
+    };
+    quote! {
+        {
+            const INTERVAL: ::sqlx_postgres_interval::Interval =
+                ::sqlx_postgres_interval::Interval::new(#months, #days, #microseconds);
+            INTERVAL
+        }
+    }
+    .into()
+}
@@ -0,0 +1,26 @@
+use sqlx_postgres_interval::Interval;
+use sqlx_postgres_interval_macros::interval;
+
+const TIMEOUT: Interval = interval!("30 minutes");
+static FOREVER: Interval = interval!("-infinity");
+
+#[test]
+fn const_and_static_items() {
+    assert!(TIMEOUT.is_identical(&Interval::new(0, 0, 1_800_000_000)));
+    assert!(FOREVER.is_identical(&Interval::NEG_INFINITY));
+}
+
+#[test]
+fn literals_match_from_str() {
+    for (literal, input) in [
+        (interval!("1 year 2 mons 3 days"), "1 year 2 mons 3 days"),
+        (interval!("P1Y2M3DT4H5M6.5S"), "P1Y2M3DT4H5M6.5S"),
+        (interval!("-01:30:00"), "-01:30:00"),
+        (interval!("1.5 months"), "1.5 months"),
+        (interval!("@ 14 seconds ago"), "@ 14 seconds ago"),
+        (interval!("infinity"), "infinity"),
+    ] {
+        let parsed: Interval = input.parse().unwrap();
+        assert!(literal.is_identical(&parsed), "{input}");
+    }
+}
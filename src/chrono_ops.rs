@@ -0,0 +1,478 @@
+//! [`IntervalArithmetic`] for `chrono`'s date and time types.
+
+use std::ops::{Add, Sub};
+
+use chrono::{
+    DateTime, Datelike, Days, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeDelta, TimeZone, Timelike,
+};
+use sqlx::error::BoxDynError;
+
+use crate::{
+    Interval, IntervalAnchor, IntervalArithmetic, OUT_OF_RANGE,
+    datetime::{self, TIMESTAMP_OUT_OF_RANGE, Tm},
+};
+
+fn add_months(local: NaiveDateTime, months: i32) -> Result<NaiveDateTime, BoxDynError> {
+    let months_abs = Months::new(months.unsigned_abs());
+    let result = if months < 0 {
+        local.checked_sub_months(months_abs)
+    } else {
+        local.checked_add_months(months_abs)
+    };
+    Ok(result.ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+}
+
+fn add_days(local: NaiveDateTime, days: i32) -> Result<NaiveDateTime, BoxDynError> {
+    let days_abs = Days::new(days.unsigned_abs().into());
+    let result = if days < 0 {
+        local.checked_sub_days(days_abs)
+    } else {
+        local.checked_add_days(days_abs)
+    };
+    Ok(result.ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+}
+
+fn add_microseconds<T>(
+    value: T,
+    microseconds: i64,
+    add: impl FnOnce(T, TimeDelta) -> Option<T>,
+) -> Result<T, BoxDynError> {
+    Ok(add(value, TimeDelta::microseconds(microseconds)).ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+}
+
+/// `timestamp2tm`
+fn tm(local: &NaiveDateTime) -> Tm {
+    Tm {
+        year: local.year(),
+        mon: local.month() as i32,
+        mday: local.day() as i32,
+        hour: local.hour() as i32,
+        min: local.minute() as i32,
+        sec: local.second() as i32,
+        usec: (local.nanosecond() / 1000) as i32,
+    }
+}
+
+fn interval_since(elapsed: TimeDelta) -> Result<Interval, BoxDynError> {
+    datetime::difference(elapsed.num_microseconds().ok_or(OUT_OF_RANGE)?.into())
+}
+
+/// `DetermineTimeZoneOffset`: a local time skipped by a forward transition
+/// is read with the offset from before it (so 02:30 in a 02:00 → 03:00 gap
+/// becomes 03:30), and a local time repeated by a backward transition is
+/// read as the later of the two instants.
+fn from_local<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> Result<DateTime<Tz>, BoxDynError> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(result) => Ok(result),
+        LocalResult::Ambiguous(_, latest) => Ok(latest),
+        LocalResult::None => {
+            // zones don't have two transitions within a day, so a day earlier
+            // is still on the offset from before the gap
+            let day_before = local
+                .checked_sub_days(Days::new(1))
+                .ok_or(TIMESTAMP_OUT_OF_RANGE)?;
+            let before = tz.offset_from_utc_datetime(&day_before).fix();
+            let utc = local
+                .checked_sub_offset(before)
+                .ok_or(TIMESTAMP_OUT_OF_RANGE)?;
+            Ok(tz.from_utc_datetime(&utc))
+        }
+    }
+}
+
+impl IntervalArithmetic for NaiveDate {
+    type Output = NaiveDateTime;
+
+    /// `date + interval`, which the server computes on the date's midnight.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<NaiveDateTime, BoxDynError> {
+        self.and_time(NaiveTime::MIN).checked_add_interval(interval)
+    }
+}
+
+impl IntervalArithmetic for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    /// `timestamp_pl_interval`
+    fn checked_add_interval(&self, interval: &Interval) -> Result<NaiveDateTime, BoxDynError> {
+        datetime::check_finite(interval)?;
+        let result = add_months(*self, interval.months)?;
+        let result = add_days(result, interval.days)?;
+        add_microseconds(result, interval.microseconds, |value, delta| {
+            value.checked_add_signed(delta)
+        })
+    }
+}
+
+impl<Tz: TimeZone> IntervalArithmetic for DateTime<Tz> {
+    type Output = DateTime<Tz>;
+
+    /// `timestamptz_pl_interval`: months and days are added to the local time
+    /// in `Tz`, so `1 day` across a DST change is 23 or 25 hours while
+    /// `24 hours` is always 24.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<DateTime<Tz>, BoxDynError> {
+        datetime::check_finite(interval)?;
+        let tz = self.timezone();
+        let mut result = self.clone();
+        // only re-resolve the local time when it changed, or an unchanged
+        // time in a repeated hour would jump to its later instant
+        if interval.months != 0 {
+            result = from_local(&tz, add_months(result.naive_local(), interval.months)?)?;
+        }
+        if interval.days != 0 {
+            result = from_local(&tz, add_days(result.naive_local(), interval.days)?)?;
+        }
+        add_microseconds(result, interval.microseconds, |value, delta| {
+            value.checked_add_signed(delta)
+        })
+    }
+}
+
+impl IntervalAnchor for NaiveDateTime {
+    type Duration = TimeDelta;
+
+    fn elapsed_since(&self, earlier: &Self) -> TimeDelta {
+        self.signed_duration_since(*earlier)
+    }
+
+    /// `timestamp_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::age(tm(self), tm(earlier), self < earlier)
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        interval_since(self.elapsed_since(earlier))
+    }
+}
+
+impl<Tz: TimeZone> IntervalAnchor for DateTime<Tz> {
+    type Duration = TimeDelta;
+
+    fn elapsed_since(&self, earlier: &Self) -> TimeDelta {
+        self.clone().signed_duration_since(earlier)
+    }
+
+    /// `timestamptz_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        let earlier_local = earlier.with_timezone(&self.timezone()).naive_local();
+        datetime::age(tm(&self.naive_local()), tm(&earlier_local), self < earlier)
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        interval_since(self.elapsed_since(earlier))
+    }
+}
+
+impl Add<Interval> for NaiveDate {
+    type Output = NaiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> NaiveDateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for NaiveDate {
+    type Output = NaiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> NaiveDateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add<Interval> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> NaiveDateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> NaiveDateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<Tz: TimeZone> Add<Interval> for DateTime<Tz> {
+    type Output = DateTime<Tz>;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> DateTime<Tz> {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<Tz: TimeZone> Sub<Interval> for DateTime<Tz> {
+    type Output = DateTime<Tz>;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> DateTime<Tz> {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone};
+    use chrono_tz::{America::New_York, Tz};
+
+    use crate::{
+        Interval, IntervalArithmetic,
+        datetime::{AGE_CASES, Civil, DURATION_CASES, ZONED_DURATION_CASES},
+    };
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Local time in New York, taking the later instant of a repeated hour.
+    fn new_york(local: Civil) -> DateTime<Tz> {
+        New_York
+            .from_local_datetime(&timestamp(local))
+            .latest()
+            .unwrap()
+    }
+
+    /// The local time and UTC offset in hours, which `==` on `DateTime`
+    /// ignores.
+    fn wall(value: &DateTime<Tz>) -> (NaiveDateTime, i32) {
+        (
+            value.naive_local(),
+            value.offset().fix().local_minus_utc() / 3600,
+        )
+    }
+
+    fn timestamp((year, month, day, hour, min, sec, micro): Civil) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month.into(), day.into())
+            .and_then(|date| date.and_hms_micro_opt(hour.into(), min.into(), sec.into(), micro))
+            .unwrap()
+    }
+
+    #[test]
+    fn age_and_difference() {
+        for &(later, earlier, age, difference, sum) in AGE_CASES {
+            let (later, earlier) = (timestamp(later), timestamp(earlier));
+            let interval = Interval::age(&later, &earlier).unwrap();
+            assert_eq!(interval.to_string(), age, "age({later}, {earlier})");
+            let elapsed = Interval::difference(&later, &earlier).unwrap();
+            assert_eq!(elapsed.to_string(), difference, "{later} - {earlier}");
+            let sum = timestamp(sum);
+            assert_eq!(earlier.checked_add_interval(&interval).unwrap(), sum);
+        }
+    }
+
+    #[test]
+    fn timestamptz_across_dst() {
+        // (start, interval, local result, offset), from the server with
+        // timezone = 'America/New_York'
+        let cases: &[(Civil, &str, Civil, i32)] = &[
+            (
+                (2024, 3, 9, 12, 0, 0, 0),
+                "1 day",
+                (2024, 3, 10, 12, 0, 0, 0),
+                -4,
+            ),
+            (
+                (2024, 3, 9, 12, 0, 0, 0),
+                "24 hours",
+                (2024, 3, 10, 13, 0, 0, 0),
+                -4,
+            ),
+            (
+                (2024, 11, 2, 12, 0, 0, 0),
+                "1 day",
+                (2024, 11, 3, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 11, 2, 12, 0, 0, 0),
+                "24 hours",
+                (2024, 11, 3, 11, 0, 0, 0),
+                -5,
+            ),
+            // into the spring-forward gap
+            (
+                (2024, 3, 9, 2, 30, 0, 0),
+                "1 day",
+                (2024, 3, 10, 3, 30, 0, 0),
+                -4,
+            ),
+            // into the fall-back fold
+            (
+                (2024, 11, 2, 1, 30, 0, 0),
+                "1 day",
+                (2024, 11, 3, 1, 30, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 3, 10, 12, 0, 0, 0),
+                "-1 day",
+                (2024, 3, 9, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 1, 31, 12, 0, 0, 0),
+                "1 mon",
+                (2024, 2, 29, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 3, 31, 12, 0, 0, 0),
+                "-1 mon",
+                (2024, 2, 29, 12, 0, 0, 0),
+                -5,
+            ),
+        ];
+        for &(start, input, local, offset) in cases {
+            let start = new_york(start);
+            let result = start.checked_add_interval(&interval(input)).unwrap();
+            assert_eq!(
+                wall(&result),
+                (timestamp(local), offset),
+                "{start} + {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn date_clamps_to_month_end() {
+        let cases = [
+            (date(2024, 1, 31), "1 mon", date(2024, 2, 29)),
+            (date(2023, 1, 31), "1 mon", date(2023, 2, 28)),
+            (date(2024, 2, 29), "1 year", date(2025, 2, 28)),
+            (date(2024, 3, 31), "-1 mon", date(2024, 2, 29)),
+            (date(2024, 5, 31), "-3 mons", date(2024, 2, 29)),
+        ];
+        for (start, input, expected) in cases {
+            let result = start.checked_add_interval(&interval(input)).unwrap();
+            assert_eq!(
+                result,
+                expected.and_hms_opt(0, 0, 0).unwrap(),
+                "{start} + {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let start = timestamp((2024, 1, 31, 12, 0, 0, 0));
+        assert_eq!(
+            start + interval("1 mon 1 day"),
+            timestamp((2024, 3, 1, 12, 0, 0, 0))
+        );
+        assert_eq!(
+            start - interval("1 day 2 hours"),
+            timestamp((2024, 1, 30, 10, 0, 0, 0))
+        );
+        assert_eq!(
+            date(2024, 1, 31) + interval("1 mon"),
+            timestamp((2024, 2, 29, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            date(2024, 3, 1) - interval("1 sec"),
+            timestamp((2024, 2, 29, 23, 59, 59, 0))
+        );
+        let zoned = new_york((2024, 3, 9, 12, 0, 0, 0));
+        assert_eq!(
+            wall(&(zoned + interval("1 day"))),
+            (timestamp((2024, 3, 10, 12, 0, 0, 0)), -4)
+        );
+        let fixed = FixedOffset::east_opt(3600).unwrap();
+        let fixed = fixed.from_local_datetime(&start).unwrap();
+        assert_eq!(
+            (fixed - interval("1 mon")).naive_local(),
+            timestamp((2023, 12, 31, 12, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_out_of_range() {
+        let _ = NaiveDate::MAX + interval("1 day");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn sub_panics_out_of_range() {
+        let _ = NaiveDateTime::MIN - interval("1 mon");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn timestamptz_add_panics_out_of_range() {
+        let _ = New_York.from_utc_datetime(&timestamp((262_142, 6, 1, 0, 0, 0, 0)))
+            + interval("1 year");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_on_infinity() {
+        let _ = date(2024, 1, 1) + Interval::INFINITY;
+    }
+
+    #[test]
+    fn to_duration() {
+        for &(anchor, input, from, until) in DURATION_CASES {
+            let (anchor, interval) = (timestamp(anchor), interval(input));
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration.num_microseconds(),
+                Some(from),
+                "{input} from {anchor}"
+            );
+            // a negative one has no `std::time::Duration`
+            assert_eq!(duration.to_std().is_ok(), from >= 0);
+            let duration = interval.to_duration_until(&anchor).unwrap();
+            assert_eq!(
+                duration.num_microseconds(),
+                Some(until),
+                "{input} until {anchor}"
+            );
+        }
+        for &(anchor, input, from, until) in ZONED_DURATION_CASES {
+            let (anchor, interval) = (new_york(anchor), interval(input));
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration.num_microseconds(),
+                Some(from),
+                "{input} from {anchor}"
+            );
+            let duration = interval.to_duration_until(&anchor).unwrap();
+            assert_eq!(
+                duration.num_microseconds(),
+                Some(until),
+                "{input} until {anchor}"
+            );
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Port of PostgreSQL's interval comparison (`interval_cmp_value`,
+//! `interval_eq`, `interval_cmp` and `interval_hash` in
+//! `src/backend/utils/adt/timestamp.c`).
+
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::{DAYS_PER_MONTH, Interval, USECS_PER_DAY};
+
+impl Interval {
+    /// `interval_cmp_value`: the interval as a single 128-bit span of
+    /// microseconds, counting a month as 30 days and a day as 24 hours.
+    fn cmp_value(&self) -> i128 {
+        let days = i128::from(self.months) * i128::from(DAYS_PER_MONTH) + i128::from(self.days);
+        days * i128::from(USECS_PER_DAY) + i128::from(self.microseconds)
+    }
+
+    /// Whether both intervals have exactly the same `months`, `days` and
+    /// `microseconds`.
+    ///
+    /// `==` compares like the server does, so `1 day` equals `24 hours` and
+    /// `1 mon` equals `30 days`; use this where those must stay distinct.
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self.months == other.months
+            && self.days == other.days
+            && self.microseconds == other.microseconds
+    }
+}
+
+impl PartialEq for Interval {
+    /// `interval_eq`
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp_value() == other.cmp_value()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    /// `interval_cmp`
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_value().cmp(&other.cmp_value())
+    }
+}
+
+impl Hash for Interval {
+    /// `interval_hash`: equal intervals hash alike however their span is
+    /// split between the fields.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cmp_value().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
+    use crate::Interval;
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn equal_like_postgres() {
+        for (lhs, rhs) in [
+            ("1 mon", "30 days"),
+            ("1 day", "24 hours"),
+            ("1 year", "360 days"),
+            ("-1 mon +30 days", "0"),
+            ("1 mon", "720:00:00"),
+        ] {
+            assert_eq!(interval(lhs), interval(rhs), "{lhs} = {rhs}");
+            assert!(
+                !interval(lhs).is_identical(&interval(rhs)),
+                "{lhs} is {rhs}"
+            );
+        }
+        assert_ne!(interval("1 mon"), interval("31 days"));
+        assert!(interval("1 mon").is_identical(&interval("30 days").justify_days().unwrap()));
+        assert!(Interval::INFINITY.is_identical(&interval("infinity")));
+    }
+
+    #[test]
+    fn equal_intervals_hash_alike() {
+        let set: HashSet<Interval> = [
+            "1 mon",
+            "30 days",
+            "720:00:00",
+            "1 day",
+            "24:00:00",
+            "1 mon -1 day 24:00:00",
+        ]
+        .into_iter()
+        .map(interval)
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&interval("2592000 seconds")));
+        assert!(set.contains(&interval("1440 minutes")));
+    }
+
+    #[test]
+    fn ordered_like_postgres() {
+        // `ORDER BY` on the server; the ends are beyond `i64` microseconds
+        let sorted = [
+            "-infinity",
+            "-178956970 years",
+            "-2147483648 days",
+            "-2562047788:00:54.775807",
+            "-1 mons",
+            "-29 days",
+            "00:00:00",
+            "1 day",
+            "1 mon -00:00:00.000001",
+            "1 mon",
+            "1 mon 1 day",
+            "31 days 00:00:00.000001",
+            "2562047788:00:54.775807",
+            "2147483647 days",
+            "178956970 years",
+            "infinity",
+        ];
+        let mut map = BTreeMap::new();
+        for (i, &s) in sorted.iter().enumerate().rev().step_by(2) {
+            map.insert(interval(s), i);
+        }
+        for (i, &s) in sorted.iter().enumerate().step_by(2) {
+            map.insert(interval(s), i);
+        }
+        let keys: Vec<_> = map.keys().map(Interval::to_string).collect();
+        assert_eq!(keys, sorted);
+        assert!(map.values().copied().eq(0..sorted.len()));
+
+        // an equal key replaces the value but keeps the first key
+        assert_eq!(map.insert(interval("30 days"), 99), Some(9));
+        assert_eq!(map.get(&interval("720:00:00")), Some(&99));
+        assert!(map.keys().nth(9).unwrap().is_identical(&interval("1 mon")));
+    }
+
+    #[test]
+    fn infinities_bound_everything() {
+        let finite = [
+            Interval::new(i32::MAX, i32::MAX, i64::MAX - 1),
+            Interval::new(i32::MIN, i32::MIN, i64::MIN + 1),
+            Interval::new(0, 0, 0),
+        ];
+        for interval in &finite {
+            assert!(Interval::NEG_INFINITY < *interval, "{interval}");
+            assert!(*interval < Interval::INFINITY, "{interval}");
+        }
+        assert!(Interval::NEG_INFINITY < Interval::INFINITY);
+        assert_eq!(
+            Interval::INFINITY.max(finite[0].clone()),
+            Interval::INFINITY
+        );
+    }
+}
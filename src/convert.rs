@@ -0,0 +1,503 @@
+//! Conversions between [`Interval`] and fixed-length durations.
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    DAYS_PER_MONTH, Interval, MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_DAY, USECS_PER_SEC,
+};
+
+/// `DAYS_PER_YEAR * SECS_PER_DAY`: a year of 365.25 days, in microseconds.
+const USECS_PER_YEAR: i64 = 36_525 * USECS_PER_DAY / 100;
+
+/// What to do with an interval's `months` and `days`, which have no fixed
+/// length, when converting it to a duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DurationPolicy {
+    /// Fail unless `months` and `days` are both zero.
+    #[default]
+    Reject,
+    /// Count them like PostgreSQL's `EXTRACT(EPOCH FROM interval)`: each
+    /// whole year of months as 365.25 days, each remaining month as 30 days
+    /// and each day as 24 hours.
+    Epoch,
+    /// Count each month as the given length and each day as 24 hours. The
+    /// months are rounded to microseconds as a whole, half to even, so a
+    /// length with nanoseconds doesn't drift over many months.
+    AverageMonth(std::time::Duration),
+}
+
+/// How to round a duration with sub-microsecond precision to the
+/// microseconds an [`Interval`] holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Drop the extra digits, rounding toward zero.
+    Truncate,
+    /// Round to the nearest microsecond, ties to even, as the server rounds
+    /// fractional seconds on input.
+    #[default]
+    HalfEven,
+    /// Round up, toward positive infinity.
+    Ceiling,
+}
+
+impl Rounding {
+    /// Round `nanoseconds` to whole microseconds.
+    fn round(self, nanoseconds: i128) -> i128 {
+        let (quotient, remainder) = (nanoseconds.div_euclid(1000), nanoseconds.rem_euclid(1000));
+        match self {
+            Rounding::Truncate if nanoseconds < 0 && remainder != 0 => quotient + 1,
+            Rounding::Truncate => quotient,
+            Rounding::HalfEven if remainder > 500 || (remainder == 500 && quotient % 2 != 0) => {
+                quotient + 1
+            }
+            Rounding::HalfEven => quotient,
+            Rounding::Ceiling if remainder != 0 => quotient + 1,
+            Rounding::Ceiling => quotient,
+        }
+    }
+}
+
+impl Interval {
+    /// An interval of `nanoseconds` rounded to microseconds.
+    fn from_nanoseconds(nanoseconds: i128, rounding: Rounding) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            months: 0,
+            days: 0,
+            microseconds: rounding
+                .round(nanoseconds)
+                .try_into()
+                .map_err(|_| "Overflow has occurred for PostgreSQL `INTERVAL`")?,
+        })
+    }
+
+    /// Convert a `std::time::Duration` with any precision, rounding it to
+    /// microseconds, e.g. one measured with `std::time::Instant`.
+    ///
+    /// `TryFrom` is the lossless alternative, failing on nanoseconds instead.
+    pub fn from_std_rounded(
+        value: std::time::Duration,
+        rounding: Rounding,
+    ) -> Result<Self, BoxDynError> {
+        Self::from_nanoseconds(value.as_nanos().try_into()?, rounding)
+    }
+
+    /// Convert a `chrono::Duration` with any precision, rounding it to
+    /// microseconds.
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_rounded(
+        value: chrono::Duration,
+        rounding: Rounding,
+    ) -> Result<Self, BoxDynError> {
+        let nanoseconds =
+            i128::from(value.num_seconds()) * 1_000_000_000 + i128::from(value.subsec_nanos());
+        Self::from_nanoseconds(nanoseconds, rounding)
+    }
+
+    /// Convert a `time::Duration` with any precision, rounding it to
+    /// microseconds.
+    #[cfg(feature = "time")]
+    pub fn from_time_rounded(
+        value: time::Duration,
+        rounding: Rounding,
+    ) -> Result<Self, BoxDynError> {
+        Self::from_nanoseconds(value.whole_nanoseconds(), rounding)
+    }
+
+    /// The total length of the interval in microseconds under `policy`.
+    /// Durations have no infinity, so the infinite intervals are refused.
+    fn total_microseconds(&self, policy: DurationPolicy) -> Result<i128, BoxDynError> {
+        if !self.is_finite() {
+            return Err("infinite interval cannot be converted to a duration".into());
+        }
+        let months = i128::from(self.months);
+        let calendar = match policy {
+            DurationPolicy::Reject if self.months != 0 || self.days != 0 => {
+                return Err(
+                    "interval with months or days has no fixed length; choose a `DurationPolicy`"
+                        .into(),
+                );
+            }
+            DurationPolicy::Reject => 0,
+            DurationPolicy::Epoch => {
+                let years = months / i128::from(MONTHS_PER_YEAR);
+                let months = months % i128::from(MONTHS_PER_YEAR);
+                years * i128::from(USECS_PER_YEAR)
+                    + months * i128::from(i64::from(DAYS_PER_MONTH) * USECS_PER_DAY)
+            }
+            DurationPolicy::AverageMonth(month) => {
+                let month = i128::try_from(month.as_nanos())?;
+                Rounding::HalfEven.round(months.checked_mul(month).ok_or(OUT_OF_RANGE)?)
+            }
+        };
+        let time =
+            i128::from(self.days) * i128::from(USECS_PER_DAY) + i128::from(self.microseconds);
+        Ok(calendar.checked_add(time).ok_or(OUT_OF_RANGE)?)
+    }
+
+    /// Convert to a `std::time::Duration`, handling `months` and `days`
+    /// according to `policy`.
+    ///
+    /// This returns an error for negative intervals, which `std::time::Duration`
+    /// cannot represent.
+    pub fn to_std_duration(
+        &self,
+        policy: DurationPolicy,
+    ) -> Result<std::time::Duration, BoxDynError> {
+        let microseconds = self.total_microseconds(policy)?;
+        if microseconds < 0 {
+            return Err("negative interval cannot be converted to `std::time::Duration`".into());
+        }
+        let (secs, micros) = split_microseconds(microseconds);
+        Ok(std::time::Duration::new(
+            u64::try_from(secs).map_err(|_| "Overflow has occurred for `std::time::Duration`")?,
+            micros as u32 * 1000,
+        ))
+    }
+
+    /// Convert to a `chrono::Duration`, handling `months` and `days`
+    /// according to `policy`.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_duration(
+        &self,
+        policy: DurationPolicy,
+    ) -> Result<chrono::Duration, BoxDynError> {
+        let (secs, micros) = split_microseconds(self.total_microseconds(policy)?);
+        i64::try_from(secs)
+            .ok()
+            .and_then(|secs| chrono::Duration::new(secs, micros as u32 * 1000))
+            .ok_or_else(|| "Overflow has occurred for `chrono::Duration`".into())
+    }
+
+    /// Convert to a `time::Duration`, handling `months` and `days` according
+    /// to `policy`.
+    #[cfg(feature = "time")]
+    pub fn to_time_duration(&self, policy: DurationPolicy) -> Result<time::Duration, BoxDynError> {
+        let microseconds = self.total_microseconds(policy)?;
+        let secs = i64::try_from(microseconds / i128::from(USECS_PER_SEC))
+            .map_err(|_| "Overflow has occurred for `time::Duration`")?;
+        // same sign as `secs`, as `time::Duration::new` wants
+        let nanos = (microseconds % i128::from(USECS_PER_SEC)) as i32 * 1000;
+        Ok(time::Duration::new(secs, nanos))
+    }
+}
+
+/// Split microseconds into whole seconds, rounded down, and the remaining
+/// microseconds in `0..1_000_000`.
+fn split_microseconds(microseconds: i128) -> (i128, i64) {
+    let usecs_per_sec = i128::from(USECS_PER_SEC);
+    (
+        microseconds.div_euclid(usecs_per_sec),
+        microseconds.rem_euclid(usecs_per_sec) as i64,
+    )
+}
+
+impl TryFrom<Interval> for std::time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert an `Interval` with no `months` or `days` to a
+    /// `std::time::Duration`; see [`Interval::to_std_duration`] for the others.
+    fn try_from(value: Interval) -> Result<Self, BoxDynError> {
+        value.to_std_duration(DurationPolicy::Reject)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Interval> for chrono::Duration {
+    type Error = BoxDynError;
+
+    /// Convert an `Interval` with no `months` or `days` to a
+    /// `chrono::Duration`; see [`Interval::to_chrono_duration`] for the others.
+    fn try_from(value: Interval) -> Result<Self, BoxDynError> {
+        value.to_chrono_duration(DurationPolicy::Reject)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Interval> for time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert an `Interval` with no `months` or `days` to a
+    /// `time::Duration`; see [`Interval::to_time_duration`] for the others.
+    fn try_from(value: Interval) -> Result<Self, BoxDynError> {
+        value.to_time_duration(DurationPolicy::Reject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{DurationPolicy, Interval, Rounding};
+
+    const NO_FIXED_LENGTH: &str =
+        "interval with months or days has no fixed length; choose a `DurationPolicy`";
+
+    /// The server's `EXTRACT(EPOCH FROM interval)`, in microseconds.
+    const EPOCH: &[(&str, i64)] = &[
+        ("1 year", 31_557_600_000_000),
+        ("1 year 1 mon 1 day", 34_236_000_000_000),
+        ("-1 year -1 mon", -34_149_600_000_000),
+        ("11 mons", 28_512_000_000_000),
+        ("-1 mon +3 days", -2_332_800_000_000),
+        ("25 mons -00:00:00.5", 65_707_199_500_000),
+        ("1 day 01:02:03.000004", 90_123_000_004),
+    ];
+
+    const MAX: i128 = i64::MAX as i128 * 1000;
+    const MIN: i128 = i64::MIN as i128 * 1000;
+
+    /// Nanoseconds, how to round them, and the microseconds of the result or
+    /// `None` if it overflows.
+    const ROUNDED: &[(i128, Rounding, Option<i64>)] = &[
+        (1500, Rounding::HalfEven, Some(2)),
+        (2500, Rounding::HalfEven, Some(2)),
+        (3500, Rounding::HalfEven, Some(4)),
+        (2499, Rounding::HalfEven, Some(2)),
+        (2501, Rounding::HalfEven, Some(3)),
+        (-1500, Rounding::HalfEven, Some(-2)),
+        (-2500, Rounding::HalfEven, Some(-2)),
+        (-2501, Rounding::HalfEven, Some(-3)),
+        (-499, Rounding::HalfEven, Some(0)),
+        (1999, Rounding::Truncate, Some(1)),
+        (-1999, Rounding::Truncate, Some(-1)),
+        (-1000, Rounding::Truncate, Some(-1)),
+        (1001, Rounding::Ceiling, Some(2)),
+        (-1001, Rounding::Ceiling, Some(-1)),
+        (-1999, Rounding::Ceiling, Some(-1)),
+        (-999, Rounding::Ceiling, Some(0)),
+        (-1000, Rounding::Ceiling, Some(-1)),
+        // the edges of `i64` microseconds
+        (MAX + 499, Rounding::HalfEven, Some(i64::MAX)),
+        (MAX + 500, Rounding::HalfEven, None),
+        (MAX + 999, Rounding::Truncate, Some(i64::MAX)),
+        (MAX + 1, Rounding::Ceiling, None),
+        (MIN - 500, Rounding::HalfEven, Some(i64::MIN)),
+        (MIN - 501, Rounding::HalfEven, None),
+        (MIN - 999, Rounding::Ceiling, Some(i64::MIN)),
+        (MIN - 1, Rounding::Truncate, Some(i64::MIN)),
+    ];
+
+    /// The average Gregorian month, 30.436875 days.
+    const GREGORIAN_MONTH: Duration = Duration::from_secs(2_629_746);
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    fn std_micros(interval: &str, policy: DurationPolicy) -> Result<i128, String> {
+        interval
+            .parse::<Interval>()
+            .unwrap()
+            .to_std_duration(policy)
+            .map(|duration| duration.as_micros() as i128)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn reject() {
+        for input in ["1 mon", "1 day", "-1 mon +1 day", "1 year -00:00:01"] {
+            let interval = interval(input);
+            let error = interval
+                .to_std_duration(DurationPolicy::Reject)
+                .unwrap_err();
+            assert_eq!(error.to_string(), NO_FIXED_LENGTH, "{input}");
+            assert!(Duration::try_from(interval).is_err(), "{input}");
+        }
+        assert_eq!(
+            Duration::try_from(interval("36:00:00.000001")).unwrap(),
+            Duration::new(129_600, 1000)
+        );
+        assert_eq!(std_micros("00:00:00", DurationPolicy::Reject), Ok(0));
+    }
+
+    #[test]
+    fn epoch_matches_extract() {
+        for &(input, expected) in EPOCH {
+            let expected = i128::from(expected);
+            if expected >= 0 {
+                assert_eq!(
+                    std_micros(input, DurationPolicy::Epoch),
+                    Ok(expected),
+                    "{input}"
+                );
+            }
+            #[cfg(feature = "chrono")]
+            assert_eq!(
+                interval(input)
+                    .to_chrono_duration(DurationPolicy::Epoch)
+                    .unwrap()
+                    .num_microseconds()
+                    .map(i128::from),
+                Some(expected),
+                "{input}"
+            );
+            #[cfg(feature = "time")]
+            assert_eq!(
+                interval(input)
+                    .to_time_duration(DurationPolicy::Epoch)
+                    .unwrap()
+                    .whole_microseconds(),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn average_month() {
+        let policy = DurationPolicy::AverageMonth(GREGORIAN_MONTH);
+        assert_eq!(std_micros("1 mon", policy), Ok(2_629_746_000_000));
+        assert_eq!(std_micros("1 year 2 days", policy), Ok(31_729_752_000_000));
+        assert_eq!(
+            std_micros("1 mon -1 day 00:00:01", policy),
+            Ok(2_543_347_000_000)
+        );
+        let policy = DurationPolicy::AverageMonth(Duration::from_secs(30 * 86_400));
+        assert_eq!(std_micros("1 year", policy), Ok(31_104_000_000_000));
+        // the nanoseconds of the month add up before rounding
+        let policy = DurationPolicy::AverageMonth(Duration::from_nanos(2_629_746_000_000_999));
+        assert_eq!(std_micros("1 mon", policy), Ok(2_629_746_000_001));
+        assert_eq!(std_micros("2 mons", policy), Ok(5_259_492_000_002));
+        assert_eq!(std_micros("1000 years", policy), Ok(31_556_952_000_011_988));
+        let policy = DurationPolicy::AverageMonth(Duration::from_nanos(2_629_746_000_000_500));
+        assert_eq!(std_micros("1 mon", policy), Ok(2_629_746_000_000));
+        assert_eq!(std_micros("3 mons", policy), Ok(7_889_238_000_002));
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            interval("-1 mon")
+                .to_chrono_duration(DurationPolicy::AverageMonth(GREGORIAN_MONTH))
+                .unwrap(),
+            chrono::Duration::seconds(-2_629_746)
+        );
+        #[cfg(feature = "time")]
+        assert_eq!(
+            interval("-1 mon")
+                .to_time_duration(DurationPolicy::AverageMonth(GREGORIAN_MONTH))
+                .unwrap(),
+            time::Duration::seconds(-2_629_746)
+        );
+    }
+
+    #[test]
+    fn negative_std_duration() {
+        const NEGATIVE: &str = "negative interval cannot be converted to `std::time::Duration`";
+        for (input, policy) in [
+            ("-00:00:00.000001", DurationPolicy::Reject),
+            ("-1 mon", DurationPolicy::Epoch),
+            ("1 day -24:00:00.000001", DurationPolicy::Epoch),
+            ("-1 year", DurationPolicy::AverageMonth(GREGORIAN_MONTH)),
+        ] {
+            assert_eq!(std_micros(input, policy), Err(NEGATIVE.into()), "{input}");
+        }
+        assert_eq!(std_micros("1 day -24:00:00", DurationPolicy::Epoch), Ok(0));
+    }
+
+    #[test]
+    fn overflow() {
+        let policy = DurationPolicy::AverageMonth(Duration::MAX);
+        let error = interval("2 mons").to_std_duration(policy).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Overflow has occurred for `std::time::Duration`"
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            interval("-1 mon")
+                .to_chrono_duration(policy)
+                .unwrap_err()
+                .to_string(),
+            "Overflow has occurred for `chrono::Duration`"
+        );
+        #[cfg(feature = "time")]
+        assert_eq!(
+            interval("-1 mon")
+                .to_time_duration(policy)
+                .unwrap_err()
+                .to_string(),
+            "Overflow has occurred for `time::Duration`"
+        );
+        // every finite interval fits with the other policies
+        let largest = Interval::new(i32::MAX - 1, i32::MAX - 1, i64::MAX - 1);
+        let expected = 178_956_970 * 31_557_600_000_000
+            + 6 * 2_592_000_000_000
+            + (i128::from(i32::MAX - 1) * 86_400_000_000 + i128::from(i64::MAX - 1));
+        let duration = largest.to_std_duration(DurationPolicy::Epoch).unwrap();
+        assert_eq!(duration.as_micros() as i128, expected);
+    }
+
+    #[test]
+    fn infinities() {
+        for interval in [Interval::INFINITY, Interval::NEG_INFINITY] {
+            for policy in [DurationPolicy::Reject, DurationPolicy::Epoch] {
+                assert_eq!(
+                    interval.to_std_duration(policy).unwrap_err().to_string(),
+                    "infinite interval cannot be converted to a duration"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rounded() {
+        const OVERFLOW: &str = "Overflow has occurred for PostgreSQL `INTERVAL`";
+        let check = |actual: Result<Interval, _>, nanoseconds, rounding, expected: Option<i64>| {
+            let actual = actual.map_err(|e: sqlx::error::BoxDynError| e.to_string());
+            let expected = expected.map(|us| Interval::new(0, 0, us));
+            assert_eq!(
+                actual,
+                expected.ok_or(OVERFLOW.into()),
+                "{nanoseconds} {rounding:?}"
+            );
+        };
+        for &(nanoseconds, rounding, expected) in ROUNDED {
+            let (secs, nanos) = (nanoseconds / 1_000_000_000, nanoseconds % 1_000_000_000);
+            if nanoseconds >= 0 {
+                let value = Duration::new(secs as u64, nanos as u32);
+                check(
+                    Interval::from_std_rounded(value, rounding),
+                    nanoseconds,
+                    rounding,
+                    expected,
+                );
+            }
+            #[cfg(feature = "chrono")]
+            {
+                let value = chrono::Duration::seconds(secs as i64)
+                    + chrono::Duration::nanoseconds(nanos as i64);
+                let actual = Interval::from_chrono_rounded(value, rounding);
+                check(actual, nanoseconds, rounding, expected);
+            }
+            #[cfg(feature = "time")]
+            {
+                let value = time::Duration::new(secs as i64, nanos as i32);
+                let actual = Interval::from_time_rounded(value, rounding);
+                check(actual, nanoseconds, rounding, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn strict_rejects_nanoseconds() {
+        const NANOSECONDS: &str = "PostgreSQL `INTERVAL` does not support nanoseconds precision";
+        let error = Interval::try_from(Duration::from_nanos(1)).unwrap_err();
+        assert_eq!(error.to_string(), NANOSECONDS);
+        assert_eq!(
+            Interval::from_std_rounded(Duration::from_nanos(1), Rounding::default()).unwrap(),
+            Interval::new(0, 0, 0)
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            Interval::try_from(chrono::Duration::nanoseconds(1))
+                .unwrap_err()
+                .to_string(),
+            NANOSECONDS
+        );
+        #[cfg(feature = "time")]
+        assert_eq!(
+            Interval::try_from(time::Duration::nanoseconds(-1))
+                .unwrap_err()
+                .to_string(),
+            NANOSECONDS
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use sqlx::error::BoxDynError;
+
+use crate::Interval;
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+use crate::{MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_SEC};
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub(crate) const TIMESTAMP_OUT_OF_RANGE: &str = "timestamp out of range";
+
+/// The server's `timestamp + 'infinity'::interval` is an infinite timestamp,
+/// which none of the libraries can represent.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub(crate) fn check_finite(interval: &Interval) -> Result<(), BoxDynError> {
+    if interval.is_finite() {
+        Ok(())
+    } else {
+        Err(TIMESTAMP_OUT_OF_RANGE.into())
+    }
+}
+
+/// Adding an [`Interval`] to a date or timestamp the way PostgreSQL's
+/// `timestamp_pl_interval` and `timestamptz_pl_interval` do: first the
+/// months, clamping the day to the end of a shorter month
+/// (`2024-01-31 + 1 mon` is `2024-02-29`), then the days in local calendar
+/// time, then the microseconds.
+pub trait IntervalArithmetic: Sized {
+    /// The type of the sum; a date becomes a timestamp, like `date + interval`.
+    type Output;
+
+    /// `self + interval`, failing with `timestamp out of range` if the result
+    /// does not fit the target type, which is always the case for an
+    /// infinite interval.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<Self::Output, BoxDynError>;
+
+    /// `self - interval`, i.e. `self + -interval`.
+    fn checked_sub_interval(&self, interval: &Interval) -> Result<Self::Output, BoxDynError> {
+        self.checked_add_interval(&interval.checked_neg()?)
+    }
+}
+
+/// A date and time an [`Interval`] can be laid against to find its exact
+/// length, with [`Interval::to_duration_from`] and
+/// [`Interval::to_duration_until`].
+pub trait IntervalAnchor: IntervalArithmetic<Output = Self> {
+    /// The library's own signed duration type.
+    type Duration;
+
+    /// The exact time elapsed from `earlier` to `self`.
+    fn elapsed_since(&self, earlier: &Self) -> Self::Duration;
+
+    /// `age(self, earlier)`; see [`Interval::age`].
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError>;
+
+    /// `self - earlier`; see [`Interval::difference`].
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError>;
+}
+
+impl Interval {
+    /// The exact length of the interval when it starts at `anchor`, i.e.
+    /// `(anchor + self) - anchor`.
+    ///
+    /// `1 mon` from `2024-02-01` is 29 days but from `2024-03-01` 31 days,
+    /// and with a zoned anchor `1 day` across a DST change is 23 or 25 hours.
+    pub fn to_duration_from<A: IntervalAnchor>(
+        &self,
+        anchor: &A,
+    ) -> Result<A::Duration, BoxDynError> {
+        Ok(anchor.checked_add_interval(self)?.elapsed_since(anchor))
+    }
+
+    /// The exact length of the interval when it ends at `anchor`, i.e.
+    /// `anchor - (anchor - self)`.
+    pub fn to_duration_until<A: IntervalAnchor>(
+        &self,
+        anchor: &A,
+    ) -> Result<A::Duration, BoxDynError> {
+        Ok(anchor.elapsed_since(&anchor.checked_sub_interval(self)?))
+    }
+
+    /// PostgreSQL's `age(later, earlier)`: the symbolic difference in years,
+    /// months and days. When the day of the month goes backwards, the days
+    /// are borrowed using the length of the month the earlier of the two
+    /// falls in, so `age(2024-03-31, 2024-02-29)` is `1 mon 2 days`.
+    ///
+    /// Adding the result back to `earlier` does not always give `later`,
+    /// since the borrow ignores the clamping to a shorter month's end:
+    /// `age(2024-03-01, 2024-01-30)` is `1 mon 2 days` too, but
+    /// `2024-01-30 + 1 mon 2 days` is `2024-03-02`.
+    ///
+    /// For zoned types, the fields are compared in `later`'s time zone.
+    pub fn age<A: IntervalAnchor>(later: &A, earlier: &A) -> Result<Self, BoxDynError> {
+        later.age_since(earlier)
+    }
+
+    /// PostgreSQL's `later - earlier` for timestamps: the exact elapsed time
+    /// with whole 24-hour periods as days, like `justify_hours`, e.g.
+    /// `1 day 01:00:00`.
+    pub fn difference<A: IntervalAnchor>(later: &A, earlier: &A) -> Result<Self, BoxDynError> {
+        later.interval_since(earlier)
+    }
+}
+
+/// `struct pg_tm`: a date and time broken down into its civil fields.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+#[derive(Clone, Copy)]
+pub(crate) struct Tm {
+    pub(crate) year: i32,
+    /// 1-based
+    pub(crate) mon: i32,
+    pub(crate) mday: i32,
+    pub(crate) hour: i32,
+    pub(crate) min: i32,
+    pub(crate) sec: i32,
+    pub(crate) usec: i32,
+}
+
+/// `day_tab[isleap(year)][mon - 1]`
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn days_in_month(year: i32, mon: i32) -> i32 {
+    match mon {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `timestamp_age`: subtract field by field, then propagate negative fields
+/// into the next higher one.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub(crate) fn age(tm1: Tm, tm2: Tm, dt1_before_dt2: bool) -> Result<Interval, BoxDynError> {
+    let sign = if dt1_before_dt2 { -1 } else { 1 };
+    let mut usec = sign * (tm1.usec - tm2.usec);
+    let mut sec = sign * (tm1.sec - tm2.sec);
+    let mut min = sign * (tm1.min - tm2.min);
+    let mut hour = sign * (tm1.hour - tm2.hour);
+    let mut mday = sign * (tm1.mday - tm2.mday);
+    let mut mon = sign * (tm1.mon - tm2.mon);
+    let mut year = sign * (tm1.year - tm2.year);
+
+    while usec < 0 {
+        usec += USECS_PER_SEC as i32;
+        sec -= 1;
+    }
+    while sec < 0 {
+        sec += 60;
+        min -= 1;
+    }
+    while min < 0 {
+        min += 60;
+        hour -= 1;
+    }
+    while hour < 0 {
+        hour += 24;
+        mday -= 1;
+    }
+    while mday < 0 {
+        // borrow the length of the month the earlier timestamp is in
+        let earlier = if dt1_before_dt2 { tm1 } else { tm2 };
+        mday += days_in_month(earlier.year, earlier.mon);
+        mon -= 1;
+    }
+    while mon < 0 {
+        mon += MONTHS_PER_YEAR as i32;
+        year -= 1;
+    }
+
+    let months = i64::from(year) * MONTHS_PER_YEAR + i64::from(mon);
+    let time = ((i64::from(hour) * 60 + i64::from(min)) * 60 + i64::from(sec)) * USECS_PER_SEC
+        + i64::from(usec);
+    Ok(Interval {
+        months: (i64::from(sign) * months)
+            .try_into()
+            .map_err(|_| OUT_OF_RANGE)?,
+        days: sign * mday,
+        microseconds: i64::from(sign) * time,
+    })
+}
+
+/// `timestamp_mi`: the elapsed microseconds, with whole days moved out by
+/// `justify_hours`.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+pub(crate) fn difference(microseconds: i128) -> Result<Interval, BoxDynError> {
+    Interval {
+        months: 0,
+        days: 0,
+        microseconds: microseconds.try_into().map_err(|_| OUT_OF_RANGE)?,
+    }
+    .justify_hours()
+}
+
+/// A civil date and time as `(year, month, day, hour, minute, second,
+/// microsecond)`, for the tests of each library's anchors.
+#[cfg(all(test, any(feature = "chrono", feature = "time", feature = "jiff")))]
+pub(crate) type Civil = (i32, u8, u8, u8, u8, u8, u32);
+
+/// `later`, `earlier`, and the server's `age(later, earlier)`,
+/// `later - earlier` and `earlier + age(later, earlier)` for them as
+/// `timestamp`s.
+#[cfg(all(test, any(feature = "chrono", feature = "time", feature = "jiff")))]
+pub(crate) const AGE_CASES: &[(Civil, Civil, &str, &str, Civil)] = &[
+    // month-end borrows, which don't always add back up to `later`
+    (
+        (2024, 3, 1, 0, 0, 0, 0),
+        (2024, 1, 30, 0, 0, 0, 0),
+        "1 mon 2 days",
+        "31 days",
+        (2024, 3, 2, 0, 0, 0, 0),
+    ),
+    (
+        (2024, 3, 31, 0, 0, 0, 0),
+        (2024, 2, 29, 0, 0, 0, 0),
+        "1 mon 2 days",
+        "31 days",
+        (2024, 3, 31, 0, 0, 0, 0),
+    ),
+    (
+        (2023, 3, 1, 0, 0, 0, 0),
+        (2023, 1, 31, 0, 0, 0, 0),
+        "1 mon 1 day",
+        "29 days",
+        (2023, 3, 1, 0, 0, 0, 0),
+    ),
+    (
+        (2001, 4, 10, 0, 0, 0, 0),
+        (1957, 6, 13, 0, 0, 0, 0),
+        "43 years 9 mons 27 days",
+        "16007 days",
+        (2001, 4, 9, 0, 0, 0, 0),
+    ),
+    // borrows from the time fields
+    (
+        (2024, 3, 1, 0, 0, 0, 0),
+        (2024, 2, 28, 12, 0, 0, 0),
+        "1 day 12:00:00",
+        "1 day 12:00:00",
+        (2024, 3, 1, 0, 0, 0, 0),
+    ),
+    (
+        (2024, 1, 1, 0, 0, 0, 500_000),
+        (2023, 12, 31, 23, 59, 59, 750_000),
+        "00:00:00.75",
+        "00:00:00.75",
+        (2024, 1, 1, 0, 0, 0, 500_000),
+    ),
+    // negative ages, which borrow using the month of the first argument,
+    // now the earlier of the two
+    (
+        (2024, 1, 30, 0, 0, 0, 0),
+        (2024, 3, 1, 0, 0, 0, 0),
+        "-1 mons -2 days",
+        "-31 days",
+        (2024, 1, 30, 0, 0, 0, 0),
+    ),
+    (
+        (2024, 2, 29, 0, 0, 0, 0),
+        (2024, 3, 31, 0, 0, 0, 0),
+        "-1 mons -2 days",
+        "-31 days",
+        (2024, 2, 27, 0, 0, 0, 0),
+    ),
+    (
+        (2024, 2, 28, 12, 0, 0, 0),
+        (2024, 3, 1, 0, 0, 0, 0),
+        "-1 days -12:00:00",
+        "-1 days -12:00:00",
+        (2024, 2, 28, 12, 0, 0, 0),
+    ),
+    (
+        (2023, 12, 31, 23, 59, 59, 750_000),
+        (2024, 1, 1, 0, 0, 0, 500_000),
+        "-00:00:00.75",
+        "-00:00:00.75",
+        (2023, 12, 31, 23, 59, 59, 750_000),
+    ),
+    (
+        (2024, 5, 15, 10, 0, 0, 0),
+        (2024, 5, 15, 10, 0, 0, 0),
+        "00:00:00",
+        "00:00:00",
+        (2024, 5, 15, 10, 0, 0, 0),
+    ),
+];
+
+/// An anchor, an interval, and the server's
+/// `EXTRACT(EPOCH FROM (anchor + interval) - anchor)` and
+/// `EXTRACT(EPOCH FROM anchor - (anchor - interval))` in microseconds, for the
+/// anchor as a `timestamp`.
+#[cfg(all(test, any(feature = "chrono", feature = "time", feature = "jiff")))]
+pub(crate) const DURATION_CASES: &[(Civil, &str, i64, i64)] = &[
+    // the month after 2024-01-31 ends on 2024-02-29, the one before on
+    // 2023-12-31
+    (
+        (2024, 1, 31, 0, 0, 0, 0),
+        "1 mon",
+        2_505_600_000_000,
+        2_678_400_000_000,
+    ),
+    (
+        (2024, 2, 1, 0, 0, 0, 0),
+        "1 mon",
+        2_505_600_000_000,
+        2_678_400_000_000,
+    ),
+    (
+        (2024, 3, 31, 0, 0, 0, 0),
+        "1 mon",
+        2_592_000_000_000,
+        2_678_400_000_000,
+    ),
+    (
+        (2024, 1, 31, 0, 0, 0, 0),
+        "1 mon 1 day",
+        2_592_000_000_000,
+        2_764_800_000_000,
+    ),
+    (
+        (2024, 2, 29, 0, 0, 0, 0),
+        "1 year",
+        31_536_000_000_000,
+        31_622_400_000_000,
+    ),
+    (
+        (2024, 1, 31, 0, 0, 0, 0),
+        "-1 mon",
+        -2_678_400_000_000,
+        -2_505_600_000_000,
+    ),
+    (
+        (2024, 1, 31, 12, 0, 0, 0),
+        "1 day 02:00:00.000001",
+        93_600_000_001,
+        93_600_000_001,
+    ),
+    (
+        (2024, 1, 31, 12, 0, 0, 0),
+        "-00:00:01.5",
+        -1_500_000,
+        -1_500_000,
+    ),
+];
+
+/// Like [`DURATION_CASES`], for the anchor as a `timestamptz` in
+/// `America/New_York`, where `1 day` is 23 hours into 2024-03-10 and 25 hours
+/// into 2024-11-03.
+#[cfg(all(test, any(feature = "chrono", feature = "jiff")))]
+pub(crate) const ZONED_DURATION_CASES: &[(Civil, &str, i64, i64)] = &[
+    (
+        (2024, 3, 9, 12, 0, 0, 0),
+        "1 day",
+        82_800_000_000,
+        86_400_000_000,
+    ),
+    (
+        (2024, 3, 10, 12, 0, 0, 0),
+        "1 day",
+        86_400_000_000,
+        82_800_000_000,
+    ),
+    (
+        (2024, 3, 9, 12, 0, 0, 0),
+        "24 hours",
+        86_400_000_000,
+        86_400_000_000,
+    ),
+    (
+        (2024, 11, 2, 12, 0, 0, 0),
+        "1 day",
+        90_000_000_000,
+        86_400_000_000,
+    ),
+    (
+        (2024, 11, 3, 12, 0, 0, 0),
+        "1 day",
+        86_400_000_000,
+        90_000_000_000,
+    ),
+    (
+        (2024, 11, 3, 12, 0, 0, 0),
+        "-1 day",
+        -90_000_000_000,
+        -86_400_000_000,
+    ),
+    (
+        (2024, 2, 10, 12, 0, 0, 0),
+        "1 mon",
+        2_502_000_000_000,
+        2_678_400_000_000,
+    ),
+];
@@ -0,0 +1,235 @@
+//! `5.days()`-style constructors on the primitive number types.
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    Interval, MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_HOUR, USECS_PER_MINUTE, USECS_PER_SEC,
+};
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Milli,
+    Micro,
+}
+
+/// `count` of `unit`, in the field that holds it.
+fn whole_units(count: i128, unit: Unit) -> Result<Interval, BoxDynError> {
+    let (months, days, microseconds) = match unit {
+        Unit::Year => (count.checked_mul(MONTHS_PER_YEAR.into()), Some(0), Some(0)),
+        Unit::Month => (Some(count), Some(0), Some(0)),
+        Unit::Week => (Some(0), count.checked_mul(7), Some(0)),
+        Unit::Day => (Some(0), Some(count), Some(0)),
+        Unit::Hour => (Some(0), Some(0), count.checked_mul(USECS_PER_HOUR.into())),
+        Unit::Minute => (Some(0), Some(0), count.checked_mul(USECS_PER_MINUTE.into())),
+        Unit::Second => (Some(0), Some(0), count.checked_mul(USECS_PER_SEC.into())),
+        Unit::Milli => (Some(0), Some(0), count.checked_mul(1000)),
+        Unit::Micro => (Some(0), Some(0), Some(count)),
+    };
+    Ok(Interval {
+        months: months.and_then(|m| m.try_into().ok()).ok_or(OUT_OF_RANGE)?,
+        days: days.and_then(|d| d.try_into().ok()).ok_or(OUT_OF_RANGE)?,
+        microseconds: microseconds
+            .and_then(|us| us.try_into().ok())
+            .ok_or(OUT_OF_RANGE)?,
+    })
+}
+
+/// Intervals of a number of units, so `1.months() + 2.days()` is
+/// `1 mon 2 days`; import the trait to use it.
+///
+/// Integers fill the field that holds the unit: `3.years()` is `36` months
+/// and `2.weeks()` `14` days. Floats multiply one unit like
+/// `interval '1 mon' * 1.5` does, spilling the fraction into the smaller
+/// fields, so `1.5.months()` is `1 mon 15 days` and `0.5.millis()` is
+/// `00:00:00.0005`.
+///
+/// # Panics
+///
+/// The unit methods panic with `interval out of range` when the result
+/// doesn't fit, and for a NaN float; the `checked_` ones return the error
+/// instead. Likewise `+` on the results is [`Interval`]'s panicking
+/// operator, and [`Interval::checked_add`] the checked one:
+/// `1.checked_months()?.checked_add(&2.checked_days()?)?`.
+pub trait IntervalExt: Sized {
+    fn checked_years(self) -> Result<Interval, BoxDynError>;
+    fn checked_months(self) -> Result<Interval, BoxDynError>;
+    fn checked_weeks(self) -> Result<Interval, BoxDynError>;
+    fn checked_days(self) -> Result<Interval, BoxDynError>;
+    fn checked_hours(self) -> Result<Interval, BoxDynError>;
+    fn checked_minutes(self) -> Result<Interval, BoxDynError>;
+    fn checked_seconds(self) -> Result<Interval, BoxDynError>;
+    fn checked_millis(self) -> Result<Interval, BoxDynError>;
+    fn checked_micros(self) -> Result<Interval, BoxDynError>;
+
+    fn years(self) -> Interval {
+        expect_in_range(self.checked_years())
+    }
+
+    fn months(self) -> Interval {
+        expect_in_range(self.checked_months())
+    }
+
+    fn weeks(self) -> Interval {
+        expect_in_range(self.checked_weeks())
+    }
+
+    fn days(self) -> Interval {
+        expect_in_range(self.checked_days())
+    }
+
+    fn hours(self) -> Interval {
+        expect_in_range(self.checked_hours())
+    }
+
+    fn minutes(self) -> Interval {
+        expect_in_range(self.checked_minutes())
+    }
+
+    fn seconds(self) -> Interval {
+        expect_in_range(self.checked_seconds())
+    }
+
+    fn millis(self) -> Interval {
+        expect_in_range(self.checked_millis())
+    }
+
+    fn micros(self) -> Interval {
+        expect_in_range(self.checked_micros())
+    }
+}
+
+fn expect_in_range(result: Result<Interval, BoxDynError>) -> Interval {
+    result.unwrap_or_else(|e| panic!("{e}"))
+}
+
+macro_rules! unit_methods {
+    ($convert:ident) => {
+        fn checked_years(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Year)
+        }
+
+        fn checked_months(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Month)
+        }
+
+        fn checked_weeks(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Week)
+        }
+
+        fn checked_days(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Day)
+        }
+
+        fn checked_hours(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Hour)
+        }
+
+        fn checked_minutes(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Minute)
+        }
+
+        fn checked_seconds(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Second)
+        }
+
+        fn checked_millis(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Milli)
+        }
+
+        fn checked_micros(self) -> Result<Interval, BoxDynError> {
+            $convert(self, Unit::Micro)
+        }
+    };
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl IntervalExt for $t {
+            unit_methods!(integer_units);
+        }
+    )*};
+}
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {$(
+        impl IntervalExt for $t {
+            unit_methods!(float_units);
+        }
+    )*};
+}
+
+fn integer_units(count: impl TryInto<i128>, unit: Unit) -> Result<Interval, BoxDynError> {
+    let count = count.try_into().map_err(|_| OUT_OF_RANGE)?;
+    whole_units(count, unit)
+}
+
+fn float_units(count: impl Into<f64>, unit: Unit) -> Result<Interval, BoxDynError> {
+    whole_units(1, unit)?.checked_mul_f64(count.into())
+}
+
+impl_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, IntervalExt};
+
+    #[test]
+    fn units() {
+        for (interval, expected) in [
+            (1.months() + 2.days(), "1 mon 2 days"),
+            (1.5.months(), "1 mon 15 days"),
+            (3.years(), "3 years"),
+            (2_u8.weeks(), "14 days"),
+            (36.hours(), "36:00:00"),
+            ((-90).minutes(), "-01:30:00"),
+            (1.5_f32.seconds(), "00:00:01.5"),
+            (0.5.millis(), "00:00:00.0005"),
+            (1_500_000_u64.micros(), "00:00:01.5"),
+            (0.5.days(), "12:00:00"),
+        ] {
+            assert_eq!(interval.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn checked_units() {
+        let sum = 1
+            .checked_months()
+            .unwrap()
+            .checked_add(&2.checked_days().unwrap());
+        assert_eq!(sum.unwrap().to_string(), "1 mon 2 days");
+        for result in [
+            178_956_971.checked_years(),
+            (i64::from(i32::MAX) + 1).checked_months(),
+            306_783_379.checked_weeks(),
+            u64::MAX.checked_micros(),
+            i128::MAX.checked_seconds(),
+            f64::NAN.checked_days(),
+            1e300.checked_hours(),
+        ] {
+            assert_eq!(result.unwrap_err().to_string(), "interval out of range");
+        }
+        assert!(
+            i32::MAX
+                .checked_months()
+                .unwrap()
+                .is_identical(&Interval::new(i32::MAX, 0, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn units_panic_out_of_range() {
+        let _ = u64::MAX.micros();
+    }
+}
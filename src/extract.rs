@@ -0,0 +1,947 @@
+//! Port of PostgreSQL's `extract(field from interval)`,
+//! `date_part(text, interval)` and `date_trunc(text, interval)`
+//! (`interval_part_common` and `interval_trunc` in
+//! `src/backend/utils/adt/timestamp.c`).
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    DAYS_PER_MONTH, Interval, MONTHS_PER_YEAR, USECS_PER_HOUR, USECS_PER_MINUTE, USECS_PER_SEC,
+};
+
+/// `SECS_PER_DAY`
+const SECS_PER_DAY: i64 = 86_400;
+
+/// A field of an interval, as named in `extract(field from interval)` and
+/// `date_trunc(field, interval)`, ordered from the smallest to the largest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IntervalPart {
+    Microseconds,
+    Milliseconds,
+    /// The seconds including their fraction, e.g. `4.5` for `00:03:04.5`
+    Second,
+    Minute,
+    /// The hours of the time, which may exceed 24
+    Hour,
+    Day,
+    /// Whole weeks in the days, `day / 7` (PostgreSQL 16 and later)
+    Week,
+    /// The months left over after whole years, `-11` to `11`
+    Month,
+    /// `month / 3 + 1`, so `1` to `4` for positive months but `-2` to `1`
+    /// for negative ones
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// The total number of seconds, with 365.25-day years, 30-day months and
+    /// 24-hour days, like [`crate::DurationPolicy::Epoch`]
+    Epoch,
+}
+
+/// `struct pg_itm`, from `interval2itm`.
+struct Itm {
+    year: i32,
+    mon: i32,
+    mday: i32,
+    hour: i64,
+    min: i32,
+    /// seconds and their fraction, in microseconds
+    usec: i64,
+}
+
+impl Itm {
+    /// `interval2itm`
+    fn new(interval: &Interval) -> Self {
+        let months = interval.months;
+        let time = interval.microseconds;
+        Self {
+            year: months / MONTHS_PER_YEAR as i32,
+            mon: months % MONTHS_PER_YEAR as i32,
+            mday: interval.days,
+            hour: time / USECS_PER_HOUR,
+            min: (time % USECS_PER_HOUR / USECS_PER_MINUTE) as i32,
+            usec: time % USECS_PER_MINUTE,
+        }
+    }
+}
+
+/// `NonFiniteIntervalPart`: fields that grow with the interval are infinite,
+/// the others (`None`) have no value.
+fn non_finite_part(part: IntervalPart, negative: bool) -> Option<f64> {
+    match part {
+        IntervalPart::Microseconds
+        | IntervalPart::Milliseconds
+        | IntervalPart::Second
+        | IntervalPart::Minute
+        | IntervalPart::Week
+        | IntervalPart::Month
+        | IntervalPart::Quarter => None,
+        IntervalPart::Hour
+        | IntervalPart::Day
+        | IntervalPart::Year
+        | IntervalPart::Decade
+        | IntervalPart::Century
+        | IntervalPart::Millennium
+        | IntervalPart::Epoch => Some(if negative {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        }),
+    }
+}
+
+impl Interval {
+    /// The exact value of `part` as fixed-point digits and their scale, so
+    /// `Second` of `00:00:04.5` is `(4_500_000, 6)`.
+    fn exact_part(&self, itm: &Itm, part: IntervalPart) -> (i128, u32) {
+        match part {
+            IntervalPart::Microseconds => (itm.usec.into(), 0),
+            IntervalPart::Milliseconds => (itm.usec.into(), 3),
+            IntervalPart::Second => (itm.usec.into(), 6),
+            IntervalPart::Minute => (itm.min.into(), 0),
+            IntervalPart::Hour => (itm.hour.into(), 0),
+            IntervalPart::Day => (itm.mday.into(), 0),
+            IntervalPart::Week => ((itm.mday / 7).into(), 0),
+            IntervalPart::Month => (itm.mon.into(), 0),
+            IntervalPart::Quarter => ((itm.mon / 3 + 1).into(), 0),
+            IntervalPart::Year => (itm.year.into(), 0),
+            IntervalPart::Decade => ((itm.year / 10).into(), 0),
+            IntervalPart::Century => ((itm.year / 100).into(), 0),
+            IntervalPart::Millennium => ((itm.year / 1000).into(), 0),
+            IntervalPart::Epoch => {
+                // every term is a whole number of quarter days
+                let secs_from_days_months = ((4 * 36_525 / 100)
+                    * i128::from(self.months / MONTHS_PER_YEAR as i32)
+                    + i128::from(4 * DAYS_PER_MONTH)
+                        * i128::from(self.months % MONTHS_PER_YEAR as i32)
+                    + 4 * i128::from(self.days))
+                    * i128::from(SECS_PER_DAY / 4);
+                (
+                    secs_from_days_months * i128::from(USECS_PER_SEC)
+                        + i128::from(self.microseconds),
+                    6,
+                )
+            }
+        }
+    }
+
+    /// `extract(part from interval)`, which returns an exact `numeric` since
+    /// PostgreSQL 14: `Second` of `00:00:04.5` is `4.500000`, with the same
+    /// scale as the server's result.
+    ///
+    /// `None` is SQL `NULL`, which the infinities give for the fields that
+    /// don't grow with the interval, such as `Minute`. For the others the
+    /// server returns `Infinity`, which a `Decimal` cannot hold, so this
+    /// returns an error.
+    #[cfg(feature = "rust_decimal")]
+    pub fn extract(
+        &self,
+        part: IntervalPart,
+    ) -> Result<Option<rust_decimal::Decimal>, BoxDynError> {
+        use rust_decimal::Decimal;
+
+        if !self.is_finite() {
+            return match non_finite_part(part, self.is_neg_infinity()) {
+                None => Ok(None),
+                Some(_) => Err("infinite field value cannot be represented as a `Decimal`".into()),
+            };
+        }
+        let (digits, scale) = self.exact_part(&Itm::new(self), part);
+        Ok(Some(Decimal::from_i128_with_scale(digits, scale)))
+    }
+
+    /// `date_trunc(unit, interval)`: zero every field smaller than `unit`, so
+    /// `1 year 5 mons 3 days 04:05:06` truncated to `Quarter` is
+    /// `1 year 3 mons`. The fields are truncated toward zero each on its own,
+    /// without carrying hours into days, so `-1 day 25:00:00` truncated to
+    /// `Day` is `-1 days`.
+    ///
+    /// `Week` fails, as months don't hold whole weeks, and so does `Epoch`,
+    /// which isn't a unit. The infinities are returned unchanged.
+    pub fn trunc(&self, unit: IntervalPart) -> Result<Self, BoxDynError> {
+        match unit {
+            IntervalPart::Week => {
+                return Err("unit \"week\" not supported for type interval".into());
+            }
+            IntervalPart::Epoch => {
+                return Err("unit \"epoch\" not recognized for type interval".into());
+            }
+            _ => {}
+        }
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+
+        let mut itm = Itm::new(self);
+        if unit >= IntervalPart::Millennium {
+            itm.year = itm.year / 1000 * 1000;
+        }
+        if unit >= IntervalPart::Century {
+            itm.year = itm.year / 100 * 100;
+        }
+        if unit >= IntervalPart::Decade {
+            itm.year = itm.year / 10 * 10;
+        }
+        if unit >= IntervalPart::Year {
+            itm.mon = 0;
+        }
+        if unit >= IntervalPart::Quarter {
+            itm.mon = 3 * (itm.mon / 3);
+        }
+        if unit >= IntervalPart::Month {
+            itm.mday = 0;
+        }
+        if unit >= IntervalPart::Day {
+            itm.hour = 0;
+        }
+        if unit >= IntervalPart::Hour {
+            itm.min = 0;
+        }
+        match unit {
+            IntervalPart::Microseconds => {}
+            IntervalPart::Milliseconds => itm.usec -= itm.usec % 1000,
+            IntervalPart::Second => itm.usec -= itm.usec % USECS_PER_SEC,
+            _ => itm.usec = 0,
+        }
+
+        // `itm2interval`; every field only got closer to zero
+        Ok(Self {
+            months: itm.year * MONTHS_PER_YEAR as i32 + itm.mon,
+            days: itm.mday,
+            microseconds: itm.hour * USECS_PER_HOUR
+                + i64::from(itm.min) * USECS_PER_MINUTE
+                + itm.usec,
+        })
+    }
+
+    /// `date_part(part, interval)`, the `double precision` result `extract`
+    /// had before PostgreSQL 14, with the same rounding.
+    ///
+    /// `None` is SQL `NULL`; see [`Interval::extract`]. The fields that grow
+    /// with the interval are infinite for the infinities.
+    pub fn date_part(&self, part: IntervalPart) -> Option<f64> {
+        if !self.is_finite() {
+            return non_finite_part(part, self.is_neg_infinity());
+        }
+        let itm = Itm::new(self);
+        Some(match part {
+            IntervalPart::Milliseconds => {
+                (itm.usec / USECS_PER_SEC) as f64 * 1000.0
+                    + (itm.usec % USECS_PER_SEC) as f64 / 1000.0
+            }
+            IntervalPart::Second => {
+                (itm.usec / USECS_PER_SEC) as f64 + (itm.usec % USECS_PER_SEC) as f64 / 1_000_000.0
+            }
+            IntervalPart::Epoch => {
+                let months = self.months;
+                self.microseconds as f64 / 1_000_000.0
+                    + (365.25 * SECS_PER_DAY as f64) * f64::from(months / MONTHS_PER_YEAR as i32)
+                    + (f64::from(DAYS_PER_MONTH) * SECS_PER_DAY as f64)
+                        * f64::from(months % MONTHS_PER_YEAR as i32)
+                    + SECS_PER_DAY as f64 * f64::from(self.days)
+            }
+            part => {
+                // the other parts are whole numbers
+                let (digits, _) = self.exact_part(&itm, part);
+                digits as f64
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalPart::{self, *};
+    use crate::Interval;
+
+    // `extract`, `date_part` and `date_trunc` on PostgreSQL 17, where
+    // `quarter` is `month / 3 + 1` even for negative months
+
+    /// Every part, in the order of the expected values below.
+    const PARTS: [IntervalPart; 14] = [
+        Microseconds,
+        Milliseconds,
+        Second,
+        Minute,
+        Hour,
+        Day,
+        Week,
+        Month,
+        Quarter,
+        Year,
+        Decade,
+        Century,
+        Millennium,
+        Epoch,
+    ];
+
+    /// Every unit `trunc` accepts, in the order of the expected values below.
+    const UNITS: [IntervalPart; 12] = [
+        Microseconds,
+        Milliseconds,
+        Second,
+        Minute,
+        Hour,
+        Day,
+        Month,
+        Quarter,
+        Year,
+        Decade,
+        Century,
+        Millennium,
+    ];
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn extract_negative_intervals() {
+        for (interval, expected) in [
+            (
+                "-1 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-1",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-2592000.000000",
+                ],
+            ),
+            (
+                "-2 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-2",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-5184000.000000",
+                ],
+            ),
+            (
+                "-3 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-3",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-7776000.000000",
+                ],
+            ),
+            (
+                "-4 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-4",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-10368000.000000",
+                ],
+            ),
+            (
+                "-11 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-11",
+                    "-2",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-28512000.000000",
+                ],
+            ),
+            (
+                "-1 years -1 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-1",
+                    "1",
+                    "-1",
+                    "0",
+                    "0",
+                    "0",
+                    "-34149600.000000",
+                ],
+            ),
+            (
+                "11 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "11",
+                    "4",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "28512000.000000",
+                ],
+            ),
+            (
+                "-1 days +25:00:00",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "25",
+                    "-1",
+                    "0",
+                    "0",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "3600.000000",
+                ],
+            ),
+            (
+                "-1 years -2 mons -3 days -04:05:06.789",
+                [
+                    "-6789000",
+                    "-6789.000",
+                    "-6.789000",
+                    "-5",
+                    "-4",
+                    "-3",
+                    "0",
+                    "-2",
+                    "1",
+                    "-1",
+                    "0",
+                    "0",
+                    "0",
+                    "-37015506.789000",
+                ],
+            ),
+            (
+                "1 year 2 mons 3 days 04:05:06.789",
+                [
+                    "6789000",
+                    "6789.000",
+                    "6.789000",
+                    "5",
+                    "4",
+                    "3",
+                    "0",
+                    "2",
+                    "1",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "37015506.789000",
+                ],
+            ),
+            (
+                "-15 days",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "-15",
+                    "-2",
+                    "0",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-1296000.000000",
+                ],
+            ),
+            (
+                "-1234 years -5 mons",
+                [
+                    "0",
+                    "0.000",
+                    "0.000000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-5",
+                    "0",
+                    "-1234",
+                    "-123",
+                    "-12",
+                    "-1",
+                    "-38955038400.000000",
+                ],
+            ),
+            (
+                "-00:00:01.5",
+                [
+                    "-1500000",
+                    "-1500.000",
+                    "-1.500000",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "1",
+                    "0",
+                    "0",
+                    "0",
+                    "0",
+                    "-1.500000",
+                ],
+            ),
+        ] {
+            let interval: Interval = interval.parse().unwrap();
+            for (part, expected) in PARTS.into_iter().zip(expected) {
+                let actual = interval.extract(part).unwrap().unwrap().to_string();
+                assert_eq!(actual, expected, "{part:?} of {interval}");
+            }
+        }
+    }
+
+    #[test]
+    fn date_part_negative_intervals() {
+        for (interval, expected) in [
+            (
+                "-1 mons",
+                [
+                    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 0.0, 0.0, -2592000.0,
+                ],
+            ),
+            (
+                "-2 mons",
+                [
+                    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -2.0, 1.0, 0.0, 0.0, 0.0, 0.0, -5184000.0,
+                ],
+            ),
+            (
+                "-3 mons",
+                [
+                    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -3.0, 0.0, 0.0, 0.0, 0.0, 0.0, -7776000.0,
+                ],
+            ),
+            (
+                "-4 mons",
+                [
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -4.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -10368000.0,
+                ],
+            ),
+            (
+                "-11 mons",
+                [
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -11.0,
+                    -2.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -28512000.0,
+                ],
+            ),
+            (
+                "-1 years -1 mons",
+                [
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -1.0,
+                    1.0,
+                    -1.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -34149600.0,
+                ],
+            ),
+            (
+                "11 mons",
+                [
+                    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 11.0, 4.0, 0.0, 0.0, 0.0, 0.0, 28512000.0,
+                ],
+            ),
+            (
+                "-1 days +25:00:00",
+                [
+                    0.0, 0.0, 0.0, 0.0, 25.0, -1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 3600.0,
+                ],
+            ),
+            (
+                "-1 years -2 mons -3 days -04:05:06.789",
+                [
+                    -6789000.0,
+                    -6789.0,
+                    -6.789,
+                    -5.0,
+                    -4.0,
+                    -3.0,
+                    0.0,
+                    -2.0,
+                    1.0,
+                    -1.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -37015506.789000005,
+                ],
+            ),
+            (
+                "1 year 2 mons 3 days 04:05:06.789",
+                [
+                    6789000.0,
+                    6789.0,
+                    6.789,
+                    5.0,
+                    4.0,
+                    3.0,
+                    0.0,
+                    2.0,
+                    1.0,
+                    1.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    37015506.789000005,
+                ],
+            ),
+            (
+                "-15 days",
+                [
+                    0.0, 0.0, 0.0, 0.0, 0.0, -15.0, -2.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, -1296000.0,
+                ],
+            ),
+            (
+                "-1234 years -5 mons",
+                [
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    -5.0,
+                    0.0,
+                    -1234.0,
+                    -123.0,
+                    -12.0,
+                    -1.0,
+                    -38955038400.0,
+                ],
+            ),
+            (
+                "-00:00:01.5",
+                [
+                    -1500000.0, -1500.0, -1.5, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0,
+                    -1.5,
+                ],
+            ),
+        ] {
+            let interval: Interval = interval.parse().unwrap();
+            for (part, expected) in PARTS.into_iter().zip(expected) {
+                let actual = interval.date_part(part).unwrap();
+                assert_eq!(actual, expected, "{part:?} of {interval}");
+            }
+        }
+    }
+
+    /// Whether each part is infinite for the infinities, in the order of
+    /// `PARTS`, as in PostgreSQL 17's `NonFiniteIntervalPart`.
+    const INFINITE_PARTS: [bool; 14] = [
+        false, false, false, false, true, true, false, false, false, true, true, true, true, true,
+    ];
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn extract_infinities() {
+        for interval in [Interval::INFINITY, Interval::NEG_INFINITY] {
+            for (part, infinite) in PARTS.into_iter().zip(INFINITE_PARTS) {
+                let actual = interval.extract(part).map_err(|e| e.to_string());
+                if infinite {
+                    assert_eq!(
+                        actual,
+                        Err("infinite field value cannot be represented as a `Decimal`".into()),
+                        "{part:?} of {interval}"
+                    );
+                } else {
+                    assert_eq!(actual, Ok(None), "{part:?} of {interval}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn date_part_infinities() {
+        for (interval, infinity) in [
+            (Interval::INFINITY, f64::INFINITY),
+            (Interval::NEG_INFINITY, f64::NEG_INFINITY),
+        ] {
+            for (part, infinite) in PARTS.into_iter().zip(INFINITE_PARTS) {
+                let expected = infinite.then_some(infinity);
+                assert_eq!(interval.date_part(part), expected, "{part:?} of {interval}");
+            }
+        }
+    }
+
+    #[test]
+    fn trunc_negative_intervals() {
+        for (interval, expected) in [
+            (
+                "-1 mons",
+                [
+                    "-1 mons", "-1 mons", "-1 mons", "-1 mons", "-1 mons", "-1 mons", "-1 mons",
+                    "00:00:00", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-2 mons",
+                [
+                    "-2 mons", "-2 mons", "-2 mons", "-2 mons", "-2 mons", "-2 mons", "-2 mons",
+                    "00:00:00", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-3 mons",
+                [
+                    "-3 mons", "-3 mons", "-3 mons", "-3 mons", "-3 mons", "-3 mons", "-3 mons",
+                    "-3 mons", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-4 mons",
+                [
+                    "-4 mons", "-4 mons", "-4 mons", "-4 mons", "-4 mons", "-4 mons", "-4 mons",
+                    "-3 mons", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-11 mons",
+                [
+                    "-11 mons", "-11 mons", "-11 mons", "-11 mons", "-11 mons", "-11 mons",
+                    "-11 mons", "-9 mons", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-1 years -1 mons",
+                [
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years -1 mons",
+                    "-1 years",
+                    "-1 years",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                ],
+            ),
+            (
+                "11 mons",
+                [
+                    "11 mons", "11 mons", "11 mons", "11 mons", "11 mons", "11 mons", "11 mons",
+                    "9 mons", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-1 days +25:00:00",
+                [
+                    "-1 days +25:00:00",
+                    "-1 days +25:00:00",
+                    "-1 days +25:00:00",
+                    "-1 days +25:00:00",
+                    "-1 days +25:00:00",
+                    "-1 days",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                ],
+            ),
+            (
+                "-1 years -2 mons -3 days -04:05:06.789",
+                [
+                    "-1 years -2 mons -3 days -04:05:06.789",
+                    "-1 years -2 mons -3 days -04:05:06.789",
+                    "-1 years -2 mons -3 days -04:05:06",
+                    "-1 years -2 mons -3 days -04:05:00",
+                    "-1 years -2 mons -3 days -04:00:00",
+                    "-1 years -2 mons -3 days",
+                    "-1 years -2 mons",
+                    "-1 years",
+                    "-1 years",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                ],
+            ),
+            (
+                "1 year 5 mons 3 days 04:05:06",
+                [
+                    "1 year 5 mons 3 days 04:05:06",
+                    "1 year 5 mons 3 days 04:05:06",
+                    "1 year 5 mons 3 days 04:05:06",
+                    "1 year 5 mons 3 days 04:05:00",
+                    "1 year 5 mons 3 days 04:00:00",
+                    "1 year 5 mons 3 days",
+                    "1 year 5 mons",
+                    "1 year 3 mons",
+                    "1 year",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                ],
+            ),
+            (
+                "-15 days",
+                [
+                    "-15 days", "-15 days", "-15 days", "-15 days", "-15 days", "-15 days",
+                    "00:00:00", "00:00:00", "00:00:00", "00:00:00", "00:00:00", "00:00:00",
+                ],
+            ),
+            (
+                "-1234 years -5 mons",
+                [
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -5 mons",
+                    "-1234 years -3 mons",
+                    "-1234 years",
+                    "-1230 years",
+                    "-1200 years",
+                    "-1000 years",
+                ],
+            ),
+            (
+                "-00:00:01.5",
+                [
+                    "-00:00:01.5",
+                    "-00:00:01.5",
+                    "-00:00:01",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                    "00:00:00",
+                ],
+            ),
+        ] {
+            let interval: Interval = interval.parse().unwrap();
+            for (unit, expected) in UNITS.into_iter().zip(expected) {
+                let actual = interval.trunc(unit).unwrap().to_string();
+                assert_eq!(actual, expected, "{interval} to {unit:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn trunc_errors_and_infinities() {
+        const WEEK: &str = "unit \"week\" not supported for type interval";
+        const EPOCH: &str = "unit \"epoch\" not recognized for type interval";
+        // the unit is checked before the infinities
+        for interval in [
+            "1 year 5 mons 3 days 04:05:06".parse().unwrap(),
+            Interval::INFINITY,
+            Interval::NEG_INFINITY,
+        ] {
+            for (unit, expected) in [(Week, WEEK), (Epoch, EPOCH)] {
+                let error = interval.trunc(unit).unwrap_err();
+                assert_eq!(error.to_string(), expected, "{interval} to {unit:?}");
+            }
+        }
+        for interval in [Interval::INFINITY, Interval::NEG_INFINITY] {
+            for unit in UNITS {
+                assert_eq!(interval.trunc(unit).unwrap(), interval, "{unit:?}");
+            }
+        }
+    }
+}
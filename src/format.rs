@@ -0,0 +1,480 @@
+//! Port of PostgreSQL's `interval_out` (`interval2itm` and `EncodeInterval` in
+//! `src/backend/utils/adt/timestamp.c` and `datetime.c`).
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    Interval, IntervalStyle, MONTHS_PER_YEAR, USECS_PER_HOUR, USECS_PER_MINUTE, USECS_PER_SEC,
+};
+
+/// Helper struct for printing an [`Interval`] in a given [`IntervalStyle`],
+/// created by [`Interval::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct IntervalDisplay<'a> {
+    interval: &'a Interval,
+    style: IntervalStyle,
+}
+
+/// `struct pg_itm`: an interval broken down into its output fields.
+#[derive(Clone, Copy)]
+struct Itm {
+    year: i32,
+    mon: i32,
+    mday: i64,
+    hour: i64,
+    min: i32,
+    sec: i32,
+    fsec: i32,
+}
+
+impl Itm {
+    /// `interval2itm`
+    fn new(interval: &Interval) -> Self {
+        let months = i64::from(interval.months);
+        let time = interval.microseconds;
+        Self {
+            year: (months / MONTHS_PER_YEAR) as i32,
+            mon: (months % MONTHS_PER_YEAR) as i32,
+            mday: i64::from(interval.days),
+            hour: time / USECS_PER_HOUR,
+            min: (time % USECS_PER_HOUR / USECS_PER_MINUTE) as i32,
+            sec: (time % USECS_PER_MINUTE / USECS_PER_SEC) as i32,
+            fsec: (time % USECS_PER_SEC) as i32,
+        }
+    }
+
+    fn has_time(&self) -> bool {
+        self.hour != 0 || self.min != 0 || self.sec != 0 || self.fsec != 0
+    }
+}
+
+/// `AppendSeconds`: absolute seconds with up to six fractional digits and no
+/// trailing zeros.
+fn write_seconds(f: &mut Formatter<'_>, sec: i32, fsec: i32, fillzeros: bool) -> fmt::Result {
+    if fillzeros {
+        write!(f, "{:02}", sec.unsigned_abs())?;
+    } else {
+        write!(f, "{}", sec.unsigned_abs())?;
+    }
+    if fsec != 0 {
+        let digits = format!("{:06}", fsec.unsigned_abs());
+        write!(f, ".{}", digits.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+/// `AddPostgresIntPart`
+fn write_postgres_part(
+    f: &mut Formatter<'_>,
+    value: i64,
+    units: &str,
+    is_zero: &mut bool,
+    is_before: &mut bool,
+) -> fmt::Result {
+    if value == 0 {
+        return Ok(());
+    }
+    write!(
+        f,
+        "{}{}{value} {units}{}",
+        if *is_zero { "" } else { " " },
+        if *is_before && value > 0 { "+" } else { "" },
+        if value != 1 { "s" } else { "" },
+    )?;
+    // each nonzero field sets is_before for (only) the next one
+    *is_before = value < 0;
+    *is_zero = false;
+    Ok(())
+}
+
+/// `AddVerboseIntPart`
+fn write_verbose_part(
+    f: &mut Formatter<'_>,
+    mut value: i64,
+    units: &str,
+    is_zero: &mut bool,
+    is_before: &mut bool,
+) -> fmt::Result {
+    if value == 0 {
+        return Ok(());
+    }
+    // the first nonzero value sets is_before
+    if *is_zero {
+        *is_before = value < 0;
+        value = value.abs();
+    } else if *is_before {
+        value = -value;
+    }
+    write!(f, " {value} {units}{}", if value == 1 { "" } else { "s" })?;
+    *is_zero = false;
+    Ok(())
+}
+
+/// `AddISO8601IntPart`
+fn write_iso8601_part(f: &mut Formatter<'_>, value: i64, units: char) -> fmt::Result {
+    if value == 0 {
+        return Ok(());
+    }
+    write!(f, "{value}{units}")
+}
+
+fn write_postgres(f: &mut Formatter<'_>, itm: &Itm) -> fmt::Result {
+    let mut is_zero = true;
+    let mut is_before = false;
+    write_postgres_part(f, itm.year.into(), "year", &mut is_zero, &mut is_before)?;
+    write_postgres_part(f, itm.mon.into(), "mon", &mut is_zero, &mut is_before)?;
+    write_postgres_part(f, itm.mday, "day", &mut is_zero, &mut is_before)?;
+    if is_zero || itm.has_time() {
+        let minus = itm.hour < 0 || itm.min < 0 || itm.sec < 0 || itm.fsec < 0;
+        write!(
+            f,
+            "{}{}{:02}:{:02}:",
+            if is_zero { "" } else { " " },
+            if minus {
+                "-"
+            } else if is_before {
+                "+"
+            } else {
+                ""
+            },
+            itm.hour.unsigned_abs(),
+            itm.min.unsigned_abs(),
+        )?;
+        write_seconds(f, itm.sec, itm.fsec, true)?;
+    }
+    Ok(())
+}
+
+fn write_postgres_verbose(f: &mut Formatter<'_>, itm: &Itm) -> fmt::Result {
+    let mut is_zero = true;
+    let mut is_before = false;
+    f.write_str("@")?;
+    write_verbose_part(f, itm.year.into(), "year", &mut is_zero, &mut is_before)?;
+    write_verbose_part(f, itm.mon.into(), "mon", &mut is_zero, &mut is_before)?;
+    write_verbose_part(f, itm.mday, "day", &mut is_zero, &mut is_before)?;
+    write_verbose_part(f, itm.hour, "hour", &mut is_zero, &mut is_before)?;
+    write_verbose_part(f, itm.min.into(), "min", &mut is_zero, &mut is_before)?;
+    if itm.sec != 0 || itm.fsec != 0 {
+        f.write_str(" ")?;
+        if itm.sec < 0 || (itm.sec == 0 && itm.fsec < 0) {
+            if is_zero {
+                is_before = true;
+            } else if !is_before {
+                f.write_str("-")?;
+            }
+        } else if is_before {
+            f.write_str("-")?;
+        }
+        write_seconds(f, itm.sec, itm.fsec, false)?;
+        // "ago" is used instead of negatives, hence the absolute value
+        let plural = itm.sec.abs() != 1 || itm.fsec != 0;
+        write!(f, " sec{}", if plural { "s" } else { "" })?;
+        is_zero = false;
+    }
+    // identically zero? then put in a unitless zero
+    if is_zero {
+        f.write_str(" 0")?;
+    }
+    if is_before {
+        f.write_str(" ago")?;
+    }
+    Ok(())
+}
+
+fn write_sql_standard(f: &mut Formatter<'_>, itm: &Itm) -> fmt::Result {
+    let Itm {
+        mut year,
+        mut mon,
+        mut mday,
+        mut hour,
+        mut min,
+        mut sec,
+        mut fsec,
+    } = *itm;
+    let has_negative =
+        year < 0 || mon < 0 || mday < 0 || hour < 0 || min < 0 || sec < 0 || fsec < 0;
+    let has_positive =
+        year > 0 || mon > 0 || mday > 0 || hour > 0 || min > 0 || sec > 0 || fsec > 0;
+    let has_year_month = year != 0 || mon != 0;
+    let has_day_time = mday != 0 || itm.has_time();
+    let sql_standard_value = !((has_negative && has_positive) || (has_year_month && has_day_time));
+
+    // SQL standard wants only one sign preceding the whole interval, which
+    // is impossible with mixed signs
+    if has_negative && sql_standard_value {
+        f.write_str("-")?;
+        year = -year;
+        mon = -mon;
+        mday = -mday;
+        hour = -hour;
+        min = -min;
+        sec = -sec;
+        fsec = -fsec;
+    }
+
+    if !has_negative && !has_positive {
+        f.write_str("0")
+    } else if !sql_standard_value {
+        // force explicit signs to avoid ambiguity with mixed-sign components
+        let year_sign = if year < 0 || mon < 0 { '-' } else { '+' };
+        let day_sign = if mday < 0 { '-' } else { '+' };
+        let sec_sign = if hour < 0 || min < 0 || sec < 0 || fsec < 0 {
+            '-'
+        } else {
+            '+'
+        };
+        write!(
+            f,
+            "{year_sign}{}-{} {day_sign}{} {sec_sign}{}:{:02}:",
+            year.unsigned_abs(),
+            mon.unsigned_abs(),
+            mday.unsigned_abs(),
+            hour.unsigned_abs(),
+            min.unsigned_abs(),
+        )?;
+        write_seconds(f, sec, fsec, true)
+    } else if has_year_month {
+        write!(f, "{year}-{mon}")
+    } else if mday != 0 {
+        write!(f, "{mday} {hour}:{min:02}:")?;
+        write_seconds(f, sec, fsec, true)
+    } else {
+        write!(f, "{hour}:{min:02}:")?;
+        write_seconds(f, sec, fsec, true)
+    }
+}
+
+fn write_iso8601(f: &mut Formatter<'_>, itm: &Itm) -> fmt::Result {
+    // special-case zero to avoid printing nothing
+    if itm.year == 0 && itm.mon == 0 && itm.mday == 0 && !itm.has_time() {
+        return f.write_str("PT0S");
+    }
+    f.write_str("P")?;
+    write_iso8601_part(f, itm.year.into(), 'Y')?;
+    write_iso8601_part(f, itm.mon.into(), 'M')?;
+    write_iso8601_part(f, itm.mday, 'D')?;
+    if itm.has_time() {
+        f.write_str("T")?;
+    }
+    write_iso8601_part(f, itm.hour, 'H')?;
+    write_iso8601_part(f, itm.min.into(), 'M')?;
+    if itm.sec != 0 || itm.fsec != 0 {
+        if itm.sec < 0 || itm.fsec < 0 {
+            f.write_str("-")?;
+        }
+        write_seconds(f, itm.sec, itm.fsec, false)?;
+        f.write_str("S")?;
+    }
+    Ok(())
+}
+
+impl Display for IntervalDisplay<'_> {
+    /// `EncodeInterval`, or `EncodeSpecialInterval` for the infinities,
+    /// which print as `infinity` and `-infinity` in every style.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.interval.is_infinity() {
+            return f.write_str("infinity");
+        }
+        if self.interval.is_neg_infinity() {
+            return f.write_str("-infinity");
+        }
+        let itm = Itm::new(self.interval);
+        match self.style {
+            IntervalStyle::Postgres => write_postgres(f, &itm),
+            IntervalStyle::PostgresVerbose => write_postgres_verbose(f, &itm),
+            IntervalStyle::SqlStandard => write_sql_standard(f, &itm),
+            IntervalStyle::Iso8601 => write_iso8601(f, &itm),
+        }
+    }
+}
+
+impl Interval {
+    /// Returns an object that implements [`Display`] for printing the interval
+    /// exactly as the server's `interval_out` does in the given `IntervalStyle`.
+    pub fn display(&self, style: IntervalStyle) -> IntervalDisplay<'_> {
+        IntervalDisplay {
+            interval: self,
+            style,
+        }
+    }
+
+    /// The interval in the server's `iso_8601` style, which `Serialize`
+    /// writes: each nonzero field with its own sign and nothing else, e.g.
+    /// `P1Y2M3DT4H5M6.5S`, `P-1DT2H` or `PT0S`.
+    pub fn to_iso8601(&self) -> String {
+        self.display(IntervalStyle::Iso8601).to_string()
+    }
+}
+
+impl Display for Interval {
+    /// Print the interval in PostgreSQL's default `postgres` style, e.g.
+    /// `1 year 2 mons 3 days 04:05:06.5`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.display(IntervalStyle::Postgres).fmt(f)
+    }
+}
+
+/// Values and the server's `postgres`, `postgres_verbose`, `sql_standard` and
+/// `iso_8601` output for them, on PostgreSQL 17.
+#[cfg(test)]
+pub(crate) const INTERVAL_OUT: &[(Interval, [&str; 4])] = &[
+    (Interval::new(0, 0, 0), ["00:00:00", "@ 0", "0", "PT0S"]),
+    (
+        Interval::new(14, 3, 14_706_000_000),
+        [
+            "1 year 2 mons 3 days 04:05:06",
+            "@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs",
+            "+1-2 +3 +4:05:06",
+            "P1Y2M3DT4H5M6S",
+        ],
+    ),
+    (
+        Interval::new(-14, -3, -14_706_000_000),
+        [
+            "-1 years -2 mons -3 days -04:05:06",
+            "@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs ago",
+            "-1-2 -3 -4:05:06",
+            "P-1Y-2M-3DT-4H-5M-6S",
+        ],
+    ),
+    (
+        Interval::new(10, 0, 0),
+        ["10 mons", "@ 10 mons", "0-10", "P10M"],
+    ),
+    (
+        Interval::new(0, -1, 7_380_000_000),
+        [
+            "-1 days +02:03:00",
+            "@ 1 day -2 hours -3 mins ago",
+            "+0-0 -1 +2:03:00",
+            "P-1DT2H3M",
+        ],
+    ),
+    (
+        Interval::new(0, 1, -7_380_000_000),
+        [
+            "1 day -02:03:00",
+            "@ 1 day -2 hours -3 mins",
+            "+0-0 +1 -2:03:00",
+            "P1DT-2H-3M",
+        ],
+    ),
+    (
+        Interval::new(-10, -3, 14_706_789_000),
+        [
+            "-10 mons -3 days +04:05:06.789",
+            "@ 10 mons 3 days -4 hours -5 mins -6.789 secs ago",
+            "-0-10 -3 +4:05:06.789",
+            "P-10M-3DT4H5M6.789S",
+        ],
+    ),
+    (
+        Interval::new(1, -1, 0),
+        [
+            "1 mon -1 days",
+            "@ 1 mon -1 days",
+            "+0-1 -1 +0:00:00",
+            "P1M-1D",
+        ],
+    ),
+    (
+        Interval::new(-3, 1, 0),
+        [
+            "-3 mons +1 day",
+            "@ 3 mons -1 days ago",
+            "-0-3 +1 +0:00:00",
+            "P-3M1D",
+        ],
+    ),
+    (
+        Interval::new(0, 0, -1),
+        [
+            "-00:00:00.000001",
+            "@ 0.000001 secs ago",
+            "-0:00:00.000001",
+            "PT-0.000001S",
+        ],
+    ),
+    (
+        Interval::new(0, 0, 500_000),
+        ["00:00:00.5", "@ 0.5 secs", "0:00:00.5", "PT0.5S"],
+    ),
+    (
+        Interval::new(0, 0, -1_250_000),
+        ["-00:00:01.25", "@ 1.25 secs ago", "-0:00:01.25", "PT-1.25S"],
+    ),
+    (
+        Interval::new(0, 0, 1_500_000),
+        ["00:00:01.5", "@ 1.5 secs", "0:00:01.5", "PT1.5S"],
+    ),
+    (
+        Interval::new(0, 0, -14_000_000),
+        ["-00:00:14", "@ 14 secs ago", "-0:00:14", "PT-14S"],
+    ),
+    (
+        Interval::new(109, -12, 47_640_000_000),
+        [
+            "9 years 1 mon -12 days +13:14:00",
+            "@ 9 years 1 mon -12 days 13 hours 14 mins",
+            "+9-1 -12 +13:14:00",
+            "P9Y1M-12DT13H14M",
+        ],
+    ),
+    (
+        Interval::new(0, 2, 14_706_000_000),
+        [
+            "2 days 04:05:06",
+            "@ 2 days 4 hours 5 mins 6 secs",
+            "2 4:05:06",
+            "P2DT4H5M6S",
+        ],
+    ),
+    (
+        Interval::new(0, -2, 0),
+        ["-2 days", "@ 2 days ago", "-2 0:00:00", "P-2D"],
+    ),
+    (
+        Interval::new(408, 0, 0),
+        ["34 years", "@ 34 years", "34-0", "P34Y"],
+    ),
+    (Interval::new(3, 0, 0), ["3 mons", "@ 3 mons", "0-3", "P3M"]),
+    (
+        Interval::new(-2_147_483_648, -2_147_483_647, -9_223_372_036_854_775_807),
+        [
+            "-178956970 years -8 mons -2147483647 days -2562047788:00:54.775807",
+            "@ 178956970 years 8 mons 2147483647 days 2562047788 hours 54.775807 secs ago",
+            "-178956970-8 -2147483647 -2562047788:00:54.775807",
+            "P-178956970Y-8M-2147483647DT-2562047788H-54.775807S",
+        ],
+    ),
+    (
+        Interval::new(2_147_483_647, 2_147_483_647, 9_223_372_036_854_775_806),
+        [
+            "178956970 years 7 mons 2147483647 days 2562047788:00:54.775806",
+            "@ 178956970 years 7 mons 2147483647 days 2562047788 hours 54.775806 secs",
+            "+178956970-7 +2147483647 +2562047788:00:54.775806",
+            "P178956970Y7M2147483647DT2562047788H54.775806S",
+        ],
+    ),
+    (Interval::INFINITY, ["infinity"; 4]),
+    (Interval::NEG_INFINITY, ["-infinity"; 4]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::INTERVAL_OUT;
+    use crate::IntervalStyle::*;
+
+    #[test]
+    fn display_matches_interval_out() {
+        for (interval, expected) in INTERVAL_OUT {
+            let styles = [Postgres, PostgresVerbose, SqlStandard, Iso8601];
+            for (style, expected) in styles.into_iter().zip(*expected) {
+                let actual = interval.display(style).to_string();
+                assert_eq!(actual, expected, "{interval:?} in {style:?}");
+            }
+            assert_eq!(interval.to_string(), expected[0]);
+            assert_eq!(interval.to_iso8601(), expected[3]);
+        }
+    }
+}
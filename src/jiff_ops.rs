@@ -0,0 +1,469 @@
+//! [`IntervalArithmetic`] for `jiff`'s date and time types.
+
+use std::ops::{Add, Sub};
+
+use jiff::{
+    SignedDuration, Span, Zoned,
+    civil::{Date, DateTime},
+};
+use sqlx::error::BoxDynError;
+
+use crate::{
+    Interval, IntervalAnchor, IntervalArithmetic,
+    datetime::{self, TIMESTAMP_OUT_OF_RANGE, Tm},
+};
+
+/// Add months to `local`, clamping the day to the end of a shorter month.
+fn add_months(local: DateTime, months: i32) -> Result<DateTime, BoxDynError> {
+    Span::new()
+        .try_months(months)
+        .and_then(|span| local.checked_add(span))
+        .map_err(|_| TIMESTAMP_OUT_OF_RANGE.into())
+}
+
+fn add_days(local: DateTime, days: i32) -> Result<DateTime, BoxDynError> {
+    Span::new()
+        .try_days(days)
+        .and_then(|span| local.checked_add(span))
+        .map_err(|_| TIMESTAMP_OUT_OF_RANGE.into())
+}
+
+/// `timestamp2tm`
+fn tm(local: &DateTime) -> Tm {
+    Tm {
+        year: local.year().into(),
+        mon: local.month().into(),
+        mday: local.day().into(),
+        hour: local.hour().into(),
+        min: local.minute().into(),
+        sec: local.second().into(),
+        usec: local.subsec_nanosecond() / 1000,
+    }
+}
+
+impl IntervalArithmetic for Date {
+    type Output = DateTime;
+
+    /// `date + interval`, which the server computes on the date's midnight.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<DateTime, BoxDynError> {
+        self.to_datetime(jiff::civil::Time::midnight())
+            .checked_add_interval(interval)
+    }
+}
+
+impl IntervalArithmetic for DateTime {
+    type Output = DateTime;
+
+    /// `timestamp_pl_interval`
+    fn checked_add_interval(&self, interval: &Interval) -> Result<DateTime, BoxDynError> {
+        datetime::check_finite(interval)?;
+        add_days(add_months(*self, interval.months)?, interval.days)?
+            .checked_add(SignedDuration::from_micros(interval.microseconds))
+            .map_err(|_| TIMESTAMP_OUT_OF_RANGE.into())
+    }
+}
+
+impl IntervalArithmetic for Zoned {
+    type Output = Zoned;
+
+    /// `timestamptz_pl_interval`: months and days are added to the civil time
+    /// in the value's time zone, so `1 day` across a DST change is 23 or 25
+    /// hours while `24 hours` is always 24.
+    ///
+    /// Like `DetermineTimeZoneOffset`, a civil time that falls in a gap or a
+    /// fold is resolved with [`jiff::tz::Disambiguation::Later`].
+    fn checked_add_interval(&self, interval: &Interval) -> Result<Zoned, BoxDynError> {
+        datetime::check_finite(interval)?;
+        let from_local = |local: DateTime| {
+            self.time_zone()
+                .to_ambiguous_zoned(local)
+                .later()
+                .map_err(|_| BoxDynError::from(TIMESTAMP_OUT_OF_RANGE))
+        };
+        let mut result = self.clone();
+        // only re-resolve the civil time when it changed, or an unchanged
+        // time in a repeated hour would jump to its later instant
+        if interval.months != 0 {
+            result = from_local(add_months(result.datetime(), interval.months)?)?;
+        }
+        if interval.days != 0 {
+            result = from_local(add_days(result.datetime(), interval.days)?)?;
+        }
+        result
+            .checked_add(SignedDuration::from_micros(interval.microseconds))
+            .map_err(|_| TIMESTAMP_OUT_OF_RANGE.into())
+    }
+}
+
+impl IntervalAnchor for DateTime {
+    type Duration = SignedDuration;
+
+    fn elapsed_since(&self, earlier: &Self) -> SignedDuration {
+        self.duration_since(*earlier)
+    }
+
+    /// `timestamp_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::age(tm(self), tm(earlier), self < earlier)
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::difference(self.elapsed_since(earlier).as_micros())
+    }
+}
+
+impl IntervalAnchor for Zoned {
+    type Duration = SignedDuration;
+
+    fn elapsed_since(&self, earlier: &Self) -> SignedDuration {
+        self.duration_since(earlier)
+    }
+
+    /// `timestamptz_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        let earlier_local = earlier.with_time_zone(self.time_zone().clone()).datetime();
+        datetime::age(tm(&self.datetime()), tm(&earlier_local), self < earlier)
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::difference(self.elapsed_since(earlier).as_micros())
+    }
+}
+
+impl Add<Interval> for Date {
+    type Output = DateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> DateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for Date {
+    type Output = DateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> DateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add<Interval> for DateTime {
+    type Output = DateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> DateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for DateTime {
+    type Output = DateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> DateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add<Interval> for Zoned {
+    type Output = Zoned;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> Zoned {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for Zoned {
+    type Output = Zoned;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> Zoned {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::{
+        SignedDuration, Zoned,
+        civil::{Date, DateTime},
+        tz::TimeZone,
+    };
+
+    use crate::{
+        Interval, IntervalArithmetic,
+        datetime::{AGE_CASES, Civil, DURATION_CASES, ZONED_DURATION_CASES},
+    };
+
+    fn interval(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    fn date(year: i16, month: i8, day: i8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    /// The local time and UTC offset in hours.
+    fn wall(value: &Zoned) -> (DateTime, i32) {
+        (value.datetime(), value.offset().seconds() / 3600)
+    }
+
+    fn timestamp((year, month, day, hour, min, sec, micro): Civil) -> DateTime {
+        let narrow = |n: u8| i8::try_from(n).unwrap();
+        DateTime::new(
+            i16::try_from(year).unwrap(),
+            narrow(month),
+            narrow(day),
+            narrow(hour),
+            narrow(min),
+            narrow(sec),
+            i32::try_from(micro).unwrap() * 1000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn age_and_difference() {
+        for &(later, earlier, age, difference, sum) in AGE_CASES {
+            let (later, earlier) = (timestamp(later), timestamp(earlier));
+            let interval = Interval::age(&later, &earlier).unwrap();
+            assert_eq!(interval.to_string(), age, "age({later}, {earlier})");
+            let elapsed = Interval::difference(&later, &earlier).unwrap();
+            assert_eq!(elapsed.to_string(), difference, "{later} - {earlier}");
+            let sum = timestamp(sum);
+            assert_eq!(earlier.checked_add_interval(&interval).unwrap(), sum);
+        }
+    }
+
+    /// A civil time in `America/New_York`'s rules as of 2007.
+    fn new_york(local: Civil) -> Zoned {
+        let tz = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        timestamp(local).to_zoned(tz).unwrap()
+    }
+
+    #[test]
+    fn timestamptz_across_dst() {
+        // (start, interval, local result, offset), from the server with
+        // timezone = 'America/New_York'
+        let cases: &[(Civil, &str, Civil, i32)] = &[
+            (
+                (2024, 3, 9, 12, 0, 0, 0),
+                "1 day",
+                (2024, 3, 10, 12, 0, 0, 0),
+                -4,
+            ),
+            (
+                (2024, 3, 9, 12, 0, 0, 0),
+                "24 hours",
+                (2024, 3, 10, 13, 0, 0, 0),
+                -4,
+            ),
+            (
+                (2024, 11, 2, 12, 0, 0, 0),
+                "1 day",
+                (2024, 11, 3, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 11, 2, 12, 0, 0, 0),
+                "24 hours",
+                (2024, 11, 3, 11, 0, 0, 0),
+                -5,
+            ),
+            // into the spring-forward gap
+            (
+                (2024, 3, 9, 2, 30, 0, 0),
+                "1 day",
+                (2024, 3, 10, 3, 30, 0, 0),
+                -4,
+            ),
+            // into the fall-back fold
+            (
+                (2024, 11, 2, 1, 30, 0, 0),
+                "1 day",
+                (2024, 11, 3, 1, 30, 0, 0),
+                -5,
+            ),
+            // from the earlier 01:30 of the fold, which only the time moves
+            (
+                (2024, 11, 3, 1, 30, 0, 0),
+                "0 days 1 hour",
+                (2024, 11, 3, 1, 30, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 3, 10, 12, 0, 0, 0),
+                "-1 day",
+                (2024, 3, 9, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 1, 31, 12, 0, 0, 0),
+                "1 mon",
+                (2024, 2, 29, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2024, 3, 31, 12, 0, 0, 0),
+                "-1 mon",
+                (2024, 2, 29, 12, 0, 0, 0),
+                -5,
+            ),
+            (
+                (2023, 5, 31, 12, 0, 0, 0),
+                "-3 mons",
+                (2023, 2, 28, 12, 0, 0, 0),
+                -5,
+            ),
+        ];
+        for &(start, input, local, offset) in cases {
+            let start = new_york(start);
+            let result = start.checked_add_interval(&interval(input)).unwrap();
+            assert_eq!(
+                wall(&result),
+                (timestamp(local), offset),
+                "{start} + {input}"
+            );
+        }
+        // `new_york` takes the earlier instant of the fold
+        assert_eq!(wall(&new_york((2024, 11, 3, 1, 30, 0, 0))).1, -4);
+    }
+
+    #[test]
+    fn date_clamps_to_month_end() {
+        let cases = [
+            (date(2024, 1, 31), "1 mon", date(2024, 2, 29)),
+            (date(2023, 1, 31), "1 mon", date(2023, 2, 28)),
+            (date(2024, 2, 29), "1 year", date(2025, 2, 28)),
+            (date(2024, 3, 31), "-1 mon", date(2024, 2, 29)),
+            (date(2024, 5, 31), "-3 mons", date(2024, 2, 29)),
+        ];
+        for (start, input, expected) in cases {
+            let result = start.checked_add_interval(&interval(input)).unwrap();
+            assert_eq!(
+                result,
+                expected.to_datetime(jiff::civil::Time::midnight()),
+                "{start} + {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let start = timestamp((2024, 1, 31, 12, 0, 0, 0));
+        assert_eq!(
+            start + interval("1 mon 1 day"),
+            timestamp((2024, 3, 1, 12, 0, 0, 0))
+        );
+        assert_eq!(
+            start - interval("1 day 2 hours"),
+            timestamp((2024, 1, 30, 10, 0, 0, 0))
+        );
+        assert_eq!(
+            date(2024, 1, 31) + interval("1 mon"),
+            timestamp((2024, 2, 29, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            date(2024, 3, 1) - interval("1 sec"),
+            timestamp((2024, 2, 29, 23, 59, 59, 0))
+        );
+        let zoned = new_york((2024, 3, 9, 12, 0, 0, 0));
+        assert_eq!(
+            wall(&(zoned + interval("1 day"))),
+            (timestamp((2024, 3, 10, 12, 0, 0, 0)), -4)
+        );
+        let zoned = new_york((2024, 3, 31, 12, 0, 0, 0));
+        assert_eq!(
+            wall(&(zoned - interval("1 mon"))),
+            (timestamp((2024, 2, 29, 12, 0, 0, 0)), -5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_out_of_range() {
+        let _ = Date::MAX + interval("1 day");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn sub_panics_out_of_range() {
+        let _ = DateTime::MIN - interval("1 mon");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn timestamptz_add_panics_out_of_range() {
+        let _ = new_york((9999, 6, 1, 0, 0, 0, 0)) + interval("1 year");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_on_infinity() {
+        let _ = date(2024, 1, 1) + Interval::INFINITY;
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn timestamptz_sub_panics_on_infinity() {
+        let _ = new_york((2024, 1, 1, 0, 0, 0, 0)) - Interval::INFINITY;
+    }
+
+    #[test]
+    fn to_duration() {
+        for &(anchor, input, from, until) in DURATION_CASES {
+            let (anchor, interval) = (timestamp(anchor), input.parse::<Interval>().unwrap());
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration,
+                SignedDuration::from_micros(from),
+                "{input} from {anchor}"
+            );
+            // a negative one has no `std::time::Duration`
+            assert_eq!(std::time::Duration::try_from(duration).is_ok(), from >= 0);
+            let duration = interval.to_duration_until(&anchor).unwrap();
+            assert_eq!(
+                duration,
+                SignedDuration::from_micros(until),
+                "{input} until {anchor}"
+            );
+        }
+        for &(anchor, input, from, until) in ZONED_DURATION_CASES {
+            let (anchor, interval) = (new_york(anchor), input.parse::<Interval>().unwrap());
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration,
+                SignedDuration::from_micros(from),
+                "{input} from {anchor}"
+            );
+            let duration = interval.to_duration_until(&anchor).unwrap();
+            assert_eq!(
+                duration,
+                SignedDuration::from_micros(until),
+                "{input} until {anchor}"
+            );
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Port of PostgreSQL's `justify_hours`, `justify_days` and
+//! `justify_interval` (`interval_justify_*` in
+//! `src/backend/utils/adt/timestamp.c`). Like the server, they return the
+//! infinities unchanged.
+
+use sqlx::error::BoxDynError;
+
+use crate::{DAYS_PER_MONTH, Interval, OUT_OF_RANGE, USECS_PER_DAY};
+
+impl Interval {
+    /// `justify_hours`: move whole 24-hour periods of the time into days,
+    /// e.g. `36:00:00` becomes `1 day 12:00:00`, then give days and time the
+    /// same sign.
+    pub fn justify_hours(&self) -> Result<Self, BoxDynError> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let mut result = self.clone();
+        let whole_days = result.microseconds / USECS_PER_DAY;
+        result.microseconds -= whole_days * USECS_PER_DAY;
+        // |whole_days| is at most about 1.07e8
+        result.days = result
+            .days
+            .checked_add(whole_days as i32)
+            .ok_or(OUT_OF_RANGE)?;
+
+        if result.days > 0 && result.microseconds < 0 {
+            result.microseconds += USECS_PER_DAY;
+            result.days -= 1;
+        } else if result.days < 0 && result.microseconds > 0 {
+            result.microseconds -= USECS_PER_DAY;
+            result.days += 1;
+        }
+        Ok(result)
+    }
+
+    /// `justify_days`: move whole 30-day periods into months, e.g. `45 days`
+    /// becomes `1 mon 15 days`, then give months and days the same sign.
+    pub fn justify_days(&self) -> Result<Self, BoxDynError> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let mut result = self.clone();
+        let whole_months = result.days / DAYS_PER_MONTH;
+        result.days -= whole_months * DAYS_PER_MONTH;
+        result.months = result
+            .months
+            .checked_add(whole_months)
+            .ok_or(OUT_OF_RANGE)?;
+
+        if result.months > 0 && result.days < 0 {
+            result.days += DAYS_PER_MONTH;
+            result.months -= 1;
+        } else if result.months < 0 && result.days > 0 {
+            result.days -= DAYS_PER_MONTH;
+            result.months += 1;
+        }
+        Ok(result)
+    }
+
+    /// `justify_interval`: [`Interval::justify_hours`] and
+    /// [`Interval::justify_days`] together, with all three fields ending up
+    /// with the same sign, e.g. `1 mon -1 hour` becomes `29 days 23:00:00`.
+    pub fn justify_interval(&self) -> Result<Self, BoxDynError> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let mut result = self.clone();
+
+        // pre-justify days if it might prevent overflow
+        if (result.days > 0 && result.microseconds > 0)
+            || (result.days < 0 && result.microseconds < 0)
+        {
+            let whole_months = result.days / DAYS_PER_MONTH;
+            result.days -= whole_months * DAYS_PER_MONTH;
+            result.months = result
+                .months
+                .checked_add(whole_months)
+                .ok_or(OUT_OF_RANGE)?;
+        }
+
+        // if we pre-justified, |days| < 30; otherwise days and time have
+        // different signs; either way this can't overflow
+        let whole_days = result.microseconds / USECS_PER_DAY;
+        result.microseconds -= whole_days * USECS_PER_DAY;
+        result.days += whole_days as i32;
+
+        let whole_months = result.days / DAYS_PER_MONTH;
+        result.days -= whole_months * DAYS_PER_MONTH;
+        result.months = result
+            .months
+            .checked_add(whole_months)
+            .ok_or(OUT_OF_RANGE)?;
+
+        if result.months > 0 && (result.days < 0 || (result.days == 0 && result.microseconds < 0)) {
+            result.days += DAYS_PER_MONTH;
+            result.months -= 1;
+        } else if result.months < 0
+            && (result.days > 0 || (result.days == 0 && result.microseconds > 0))
+        {
+            result.days -= DAYS_PER_MONTH;
+            result.months += 1;
+        }
+
+        if result.days > 0 && result.microseconds < 0 {
+            result.microseconds += USECS_PER_DAY;
+            result.days -= 1;
+        } else if result.days < 0 && result.microseconds > 0 {
+            result.microseconds -= USECS_PER_DAY;
+            result.days += 1;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::error::BoxDynError;
+
+    use crate::Interval;
+
+    type Justify = fn(&Interval) -> Result<Interval, BoxDynError>;
+
+    #[test]
+    fn justify_matches_postgres() {
+        // `interval.sql`'s cases, mixed signs and the overflow boundaries on
+        // PostgreSQL
+        let cases: [(Justify, &str, Result<&str, &str>); 23] = [
+            (
+                Interval::justify_hours,
+                "6 months 3 days 52 hours 3 minutes 2 seconds",
+                Ok("6 mons 5 days 04:03:02"),
+            ),
+            (
+                Interval::justify_days,
+                "6 months 36 days 5 hours 4 minutes 3 seconds",
+                Ok("7 mons 6 days 05:04:03"),
+            ),
+            (
+                Interval::justify_hours,
+                "2147483647 days 24 hrs",
+                Err("interval out of range"),
+            ),
+            (
+                Interval::justify_days,
+                "2147483647 months 30 days",
+                Err("interval out of range"),
+            ),
+            (Interval::justify_hours, "-1 day 25 hours", Ok("01:00:00")),
+            (Interval::justify_hours, "1 day -25 hours", Ok("-01:00:00")),
+            (
+                Interval::justify_hours,
+                "-49:00:00",
+                Ok("-2 days -01:00:00"),
+            ),
+            (Interval::justify_days, "-1 mon 35 days", Ok("5 days")),
+            (Interval::justify_days, "1 mon -35 days", Ok("-5 days")),
+            (Interval::justify_days, "-65 days", Ok("-2 mons -5 days")),
+            (
+                Interval::justify_interval,
+                "1 month -1 hour",
+                Ok("29 days 23:00:00"),
+            ),
+            (
+                Interval::justify_interval,
+                "-1 month 1 hour",
+                Ok("-29 days -23:00:00"),
+            ),
+            (Interval::justify_interval, "1 mon -31 days", Ok("-1 days")),
+            (
+                Interval::justify_interval,
+                "-1 mon 29 days 25:00:00",
+                Ok("01:00:00"),
+            ),
+            (
+                Interval::justify_interval,
+                "1 day -25:00:00",
+                Ok("-01:00:00"),
+            ),
+            (
+                Interval::justify_interval,
+                "2147483647 days 24 hrs",
+                Ok("5965232 years 4 mons 8 days"),
+            ),
+            (
+                Interval::justify_interval,
+                "-2147483648 days -24 hrs",
+                Ok("-5965232 years -4 mons -9 days"),
+            ),
+            (
+                Interval::justify_interval,
+                "2147483647 months 30 days",
+                Err("interval out of range"),
+            ),
+            (
+                Interval::justify_interval,
+                "-2147483648 months -30 days",
+                Err("interval out of range"),
+            ),
+            (
+                Interval::justify_interval,
+                "2147483647 months 30 days -24 hrs",
+                Ok("178956970 years 7 mons 29 days"),
+            ),
+            (
+                Interval::justify_interval,
+                "-2147483648 months -30 days 24 hrs",
+                Ok("-178956970 years -8 mons -29 days"),
+            ),
+            (
+                Interval::justify_interval,
+                "2147483647 months -30 days 1440 hrs",
+                Err("interval out of range"),
+            ),
+            (
+                Interval::justify_interval,
+                "-2147483648 months 30 days -1440 hrs",
+                Err("interval out of range"),
+            ),
+        ];
+        for (justify, input, expected) in cases {
+            let actual = justify(&input.parse().unwrap())
+                .map(|interval| interval.to_string())
+                .map_err(|e| e.to_string());
+            assert_eq!(
+                actual,
+                expected.map(str::to_owned).map_err(str::to_owned),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn justify_keeps_infinities() {
+        for justify in [
+            Interval::justify_hours,
+            Interval::justify_days,
+            Interval::justify_interval,
+        ] {
+            for infinity in [Interval::INFINITY, Interval::NEG_INFINITY] {
+                assert!(justify(&infinity).unwrap().is_identical(&infinity));
+            }
+        }
+    }
+}
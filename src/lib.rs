@@ -1,22 +1,68 @@
-use std::mem;
+use std::{mem, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 use sqlx::{
     Decode, Encode, Postgres, Type,
     encode::IsNull,
     error::BoxDynError,
-    postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, types::PgInterval},
+    postgres::{
+        PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, types::PgInterval,
+    },
 };
 
+#[cfg(feature = "chrono")]
+mod chrono_ops;
+mod cmp;
+mod convert;
+mod datetime;
+mod ext;
+mod extract;
+mod format;
+#[cfg(feature = "jiff")]
+mod jiff_ops;
+mod justify;
+mod make;
+mod ops;
+mod parse;
+mod qualified;
+mod style;
+mod template;
+#[cfg(feature = "time")]
+mod time_ops;
+
+pub use convert::{DurationPolicy, Rounding};
+pub use datetime::{IntervalAnchor, IntervalArithmetic};
+pub use ext::IntervalExt;
+pub use extract::IntervalPart;
+pub use format::IntervalDisplay;
+pub use make::{IntervalBuilder, IntervalComponents};
+pub use qualified::{
+    DayTimeInterval, IntervalFields, IntervalQualifier, QualifiedInterval, YearMonthInterval,
+    fields,
+};
+pub use style::IntervalStyle;
+
+pub(crate) const OUT_OF_RANGE: &str = "interval out of range";
+
+pub(crate) const MONTHS_PER_YEAR: i64 = 12;
+pub(crate) const DAYS_PER_MONTH: i32 = 30;
+pub(crate) const USECS_PER_SEC: i64 = 1_000_000;
+pub(crate) const USECS_PER_MINUTE: i64 = 60 * USECS_PER_SEC;
+pub(crate) const USECS_PER_HOUR: i64 = 60 * USECS_PER_MINUTE;
+pub(crate) const USECS_PER_DAY: i64 = 24 * USECS_PER_HOUR;
+
 /// A type that mimics [`sqlx::postgres::types::PgInterval`] but provides
 /// both [`serde::Serialize`] and [`serde::Deserialize`]
-/// into and from ISO 8601 string format.
+/// into and from ISO 8601 string format. Deserializing also accepts every
+/// other format PostgreSQL outputs, so intervals inside server-built JSON
+/// round-trip regardless of `IntervalStyle`.
 ///
 /// ISO 8601 Duration Format:
 /// `P(n)Y(n)M(n)DT(n)H(n)M(n)S`
 /// Where:
 /// P - "period"/duration designator (always present at beginning)
-/// (n) - integer
+/// (n) - number, which may be negative (`P-1DT2H`) and, for the seconds,
+///       fractional (`PT1.5S`); zero fields are left out (`PT0S` if all are)
 /// Y - follows number of years
 /// M - follows number of months
 /// W - follows number of weeks
@@ -29,58 +75,157 @@ use sqlx::{
 /// See also:
 ///   - https://en.wikipedia.org/wiki/ISO_8601#Durations
 ///   - https://www.digi.com/resources/documentation/digidocs/90001488-13/reference/r_iso_8601_duration_format.htm
-
-#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality, ordering and hashing follow the server's `interval_cmp`: a month
+/// counts as 30 days and a day as 24 hours, so `1 day == 24 hours`. Use
+/// [`Interval::is_identical`] to compare the fields themselves.
+///
+/// With the `serde-struct` feature, the interval is instead serialized and
+/// deserialized as its raw fields, `{ "months": 1, "days": 2, "microseconds": 3 }`.
+#[cfg_attr(all(feature = "ts-rs", feature = "serde-struct"), derive(ts_rs::TS))]
+#[cfg_attr(feature = "serde-struct", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
 pub struct Interval {
     pub months: i32,
     pub days: i32,
     pub microseconds: i64,
 }
 
+impl Interval {
+    /// PostgreSQL 17's `'infinity'::interval`, later than every finite
+    /// interval. It is stored as every field at its maximum, so no finite
+    /// interval can have those fields.
+    pub const INFINITY: Self = Self {
+        months: i32::MAX,
+        days: i32::MAX,
+        microseconds: i64::MAX,
+    };
+
+    /// PostgreSQL 17's `'-infinity'::interval`, earlier than every finite
+    /// interval, stored as every field at its minimum.
+    pub const NEG_INFINITY: Self = Self {
+        months: i32::MIN,
+        days: i32::MIN,
+        microseconds: i64::MIN,
+    };
+
+    /// An interval of the given fields, as stored on the server.
+    pub const fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Whether the interval is neither [`Interval::INFINITY`] nor
+    /// [`Interval::NEG_INFINITY`], like `isfinite(interval)`.
+    pub const fn is_finite(&self) -> bool {
+        !self.is_infinity() && !self.is_neg_infinity()
+    }
+
+    /// `INTERVAL_IS_NOEND`
+    pub(crate) const fn is_infinity(&self) -> bool {
+        self.months == i32::MAX && self.days == i32::MAX && self.microseconds == i64::MAX
+    }
+
+    /// `INTERVAL_IS_NOBEGIN`
+    pub(crate) const fn is_neg_infinity(&self) -> bool {
+        self.months == i32::MIN && self.days == i32::MIN && self.microseconds == i64::MIN
+    }
+
+    /// Parse `s` like PostgreSQL's `interval_in` does in a session using the
+    /// given `IntervalStyle`.
+    ///
+    /// Only [`IntervalStyle::SqlStandard`] reads input differently: a leading
+    /// `-` applies to every field unless another field has an explicit sign,
+    /// so `-1 2:03:04` is `-1 days -02:03:04` rather than `-1 days +02:03:04`.
+    pub fn parse_with_style(s: &str, style: IntervalStyle) -> Result<Self, BoxDynError> {
+        parse::parse_interval(s, style, IntervalFields::All)
+    }
+
+    /// Parse an ISO 8601 duration: the format with designators, where any
+    /// field may be negative or fractional and weeks combine with the other
+    /// units (`P1Y-2M1W3.5DT4H`), or the alternative format
+    /// (`P0001-02-03T04:05:06`), as `interval_in` reads them. A leading
+    /// `-` negates every field, as ISO 8601-2 allows, so `-P1DT2H` is
+    /// `P-1DT-2H`; the server itself rejects that form. `infinity` and
+    /// `-infinity` are accepted too, so [`Interval::to_iso8601`] output
+    /// always parses back.
+    pub fn parse_iso8601(s: &str) -> Result<Self, BoxDynError> {
+        parse::parse_iso8601(s)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = BoxDynError;
+
+    /// Parse anything PostgreSQL's `interval_in` accepts with the default
+    /// `IntervalStyle`: `1 year 2 mons`, `3 hrs`, `2 centuries`,
+    /// `@ 1 day 2 hours ago`, `1 day 02:03:04.5`, `1-2 3 4:05:06`,
+    /// `P1Y2M3DT4H5M6.5S`, `P0001-02-03T04:05:06`, and so on. Fractional
+    /// units cascade into the smaller fields the same way, e.g. `1.5 months`
+    /// is `1 mon 15 days`.
+    fn from_str(s: &str) -> Result<Self, BoxDynError> {
+        parse::parse_interval(s, IntervalStyle::Postgres, IntervalFields::All)
+    }
+}
+
+#[cfg(not(feature = "serde-struct"))]
 impl Serialize for Interval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let Self {
-            months,
-            days,
-            microseconds,
-        } = self.clone();
-        let pgi = pg_interval::Interval {
-            months,
-            days,
-            microseconds,
-        };
-        serializer.serialize_str(&pgi.to_iso_8601())
+        serializer.collect_str(&self.display(IntervalStyle::Iso8601))
     }
 }
 
+#[cfg(not(feature = "serde-struct"))]
 impl<'de> Deserialize<'de> for Interval {
+    /// Deserialize from a string in any format PostgreSQL's `interval_in`
+    /// accepts. Besides the ISO 8601 written by `Serialize`, this reads the
+    /// intervals the server itself puts into JSON (`row_to_json`, `json_agg`,
+    /// ...) in any `IntervalStyle`, e.g. `"01:30:00"` or `"2 days 03:00:00"`,
+    /// and ISO 8601 durations with a leading sign, like
+    /// [`Interval::parse_iso8601`].
     fn deserialize<D>(deserializer: D) -> Result<Interval, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let pgi = pg_interval::Interval::from_iso(&s).map_err(|error| {
-            serde::de::Error::custom(match error {
-                pg_interval::ParseError::ParseIntErr(parse_int_error) => {
-                    parse_int_error.to_string()
-                }
-                pg_interval::ParseError::ParseFloatErr(parse_float_error) => {
-                    parse_float_error.to_string()
-                }
-                pg_interval::ParseError::InvalidYearMonth(invalid_year_month) => invalid_year_month,
-                pg_interval::ParseError::InvalidTime(invalid_time) => invalid_time,
-                pg_interval::ParseError::InvalidInterval(invalid_interval) => invalid_interval,
-            })
-        })?;
-        Ok(Interval {
-            months: pgi.months,
-            days: pgi.days,
-            microseconds: pgi.microseconds,
-        })
+        parse::parse_deserialized(&String::deserialize(deserializer)?, IntervalFields::All)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Exports ``type Interval = `P${string}` | "infinity" | "-infinity";``, the
+/// strings `Serialize` writes, instead of the struct fields.
+#[cfg(all(feature = "ts-rs", not(feature = "serde-struct")))]
+impl ts_rs::TS for Interval {
+    type WithoutGenerics = Self;
+
+    fn name() -> String {
+        "Interval".to_owned()
+    }
+
+    fn inline() -> String {
+        r#"`P${string}` | "infinity" | "-infinity""#.to_owned()
+    }
+
+    fn inline_flattened() -> String {
+        panic!("{} cannot be flattened", Self::name())
+    }
+
+    fn decl() -> String {
+        format!("type {} = {};", Self::name(), Self::inline())
+    }
+
+    fn decl_concrete() -> String {
+        Self::decl()
+    }
+
+    fn output_path() -> Option<&'static std::path::Path> {
+        Some(std::path::Path::new("Interval.ts"))
     }
 }
 
@@ -97,16 +242,34 @@ impl PgHasArrayType for Interval {
 }
 
 impl<'de> Decode<'de, Postgres> for Interval {
+    /// Decode either wire format. Text values are parsed like `interval_in`
+    /// with `IntervalStyle` `sql_standard` sign rules: the server never emits
+    /// the sign-ambiguous forms in its other styles, so this reads the output
+    /// of all four styles correctly.
     fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
-        let PgInterval {
-            months,
-            days,
-            microseconds,
-        } = PgInterval::decode(value)?;
+        Self::decode_bytes(value.format(), value.as_bytes()?)
+    }
+}
+
+impl Interval {
+    /// `Decode` on the raw bytes of a value in `format`.
+    pub(crate) fn decode_bytes(format: PgValueFormat, bytes: &[u8]) -> Result<Self, BoxDynError> {
+        if format == PgValueFormat::Text {
+            return parse::parse_interval(
+                std::str::from_utf8(bytes)?,
+                IntervalStyle::SqlStandard,
+                IntervalFields::All,
+            );
+        }
+
+        // `interval_recv`
+        if bytes.len() != 16 {
+            return Err(format!("expected 16 bytes for `INTERVAL`, got {}", bytes.len()).into());
+        }
         Ok(Interval {
-            months,
-            days,
-            microseconds,
+            months: i32::from_be_bytes(bytes[12..].try_into()?),
+            days: i32::from_be_bytes(bytes[8..12].try_into()?),
+            microseconds: i64::from_be_bytes(bytes[..8].try_into()?),
         })
     }
 }
@@ -137,9 +300,9 @@ impl TryFrom<std::time::Duration> for Interval {
     /// Convert a `std::time::Duration` to a `PgInterval`
     ///
     /// This returns an error if there is a loss of precision using nanoseconds or if there is a
-    /// microsecond overflow.
+    /// microsecond overflow. Use [`Interval::from_std_rounded`] to round instead.
     fn try_from(value: std::time::Duration) -> Result<Self, BoxDynError> {
-        if value.as_nanos() % 1000 != 0 {
+        if !value.as_nanos().is_multiple_of(1000) {
             return Err("PostgreSQL `INTERVAL` does not support nanoseconds precision".into());
         }
 
@@ -158,7 +321,7 @@ impl TryFrom<chrono::Duration> for Interval {
     /// Convert a `chrono::Duration` to an `Interval`.
     ///
     /// This returns an error if there is a loss of precision using nanoseconds or if there is a
-    /// nanosecond overflow.
+    /// nanosecond overflow. Use [`Interval::from_chrono_rounded`] to round instead.
     fn try_from(value: chrono::Duration) -> Result<Self, BoxDynError> {
         value
             .num_nanoseconds()
@@ -194,7 +357,7 @@ impl TryFrom<time::Duration> for Interval {
     /// Convert a `time::Duration` to a `PgInterval`.
     ///
     /// This returns an error if there is a loss of precision using nanoseconds or if there is a
-    /// microsecond overflow.
+    /// microsecond overflow. Use [`Interval::from_time_rounded`] to round instead.
     fn try_from(value: time::Duration) -> Result<Self, BoxDynError> {
         if value.whole_nanoseconds() % 1000 != 0 {
             return Err("PostgreSQL `INTERVAL` does not support nanoseconds precision".into());
@@ -207,3 +370,175 @@ impl TryFrom<time::Duration> for Interval {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::postgres::PgValueFormat;
+
+    use crate::{Interval, format::INTERVAL_OUT};
+
+    #[test]
+    fn decode_text_in_every_style() {
+        for (interval, output) in INTERVAL_OUT {
+            for text in output {
+                let decoded = Interval::decode_bytes(PgValueFormat::Text, text.as_bytes()).unwrap();
+                assert!(decoded.is_identical(interval), "{text}: {decoded:?}");
+            }
+        }
+        // the sign-ambiguous forms, which only `sql_standard` emits
+        for (text, expected) in [
+            ("-1 days +02:00:00", Interval::new(0, -1, 7_200_000_000)),
+            ("-1-2 +3 -4:05:06", Interval::new(-14, 3, -14_706_000_000)),
+            ("-1-2 3 4:05:06", Interval::new(-14, -3, -14_706_000_000)),
+            ("@ 1 day ago", Interval::new(0, -1, 0)),
+            (
+                "P-1Y-2M3DT-4H-5M-6S",
+                Interval::new(-14, 3, -14_706_000_000),
+            ),
+        ] {
+            let decoded = Interval::decode_bytes(PgValueFormat::Text, text.as_bytes()).unwrap();
+            assert!(decoded.is_identical(&expected), "{text}: {decoded:?}");
+        }
+        let error = Interval::decode_bytes(PgValueFormat::Text, b"1 fortnight").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"invalid input syntax for type interval: "1 fortnight""#
+        );
+    }
+
+    /// `interval_send`
+    fn binary(months: i32, days: i32, microseconds: i64) -> Vec<u8> {
+        [
+            &microseconds.to_be_bytes()[..],
+            &days.to_be_bytes(),
+            &months.to_be_bytes(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn decode_binary() {
+        let bytes = binary(-14, 3, -14_706_000_000);
+        let decoded = Interval::decode_bytes(PgValueFormat::Binary, &bytes).unwrap();
+        assert!(decoded.is_identical(&Interval::new(-14, 3, -14_706_000_000)));
+        let bytes = binary(i32::MAX, i32::MAX, i64::MAX);
+        let decoded = Interval::decode_bytes(PgValueFormat::Binary, &bytes).unwrap();
+        assert!(decoded.is_infinity());
+        let error = Interval::decode_bytes(PgValueFormat::Binary, &bytes[..12]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected 16 bytes for `INTERVAL`, got 12"
+        );
+    }
+
+    #[cfg(not(feature = "serde-struct"))]
+    #[test]
+    fn deserialize_server_output() {
+        let from_json = |s: &str| serde_json::from_str::<Interval>(&format!("\"{s}\""));
+        for (interval, output) in INTERVAL_OUT {
+            for text in output {
+                let deserialized = from_json(text).unwrap();
+                assert!(
+                    deserialized.is_identical(interval),
+                    "{text}: {deserialized:?}"
+                );
+            }
+        }
+        for (s, expected) in [
+            ("-1 days +02:00:00", Interval::new(0, -1, 7_200_000_000)),
+            ("1 year 2 mons", Interval::new(14, 0, 0)),
+            ("P1Y2M", Interval::new(14, 0, 0)),
+            ("@ 1 day ago", Interval::new(0, -1, 0)),
+            ("-1-2 +3 -4:05:06", Interval::new(-14, 3, -14_706_000_000)),
+            ("infinity", Interval::INFINITY),
+            ("-infinity", Interval::NEG_INFINITY),
+            // ISO 8601 with a leading sign, which the server rejects
+            ("-P1DT2H", Interval::new(0, -1, -7_200_000_000)),
+            ("-P1Y-2M", Interval::new(-10, 0, 0)),
+            ("+P1W", Interval::new(0, 7, 0)),
+            ("-PT0.5S", Interval::new(0, 0, -500_000)),
+        ] {
+            let deserialized = from_json(s).unwrap();
+            assert!(
+                deserialized.is_identical(&expected),
+                "{s}: {deserialized:?}"
+            );
+        }
+        for (s, error) in [
+            ("-P", r#"invalid input syntax for type interval: "-P""#),
+            (
+                "1 fortnight",
+                r#"invalid input syntax for type interval: "1 fortnight""#,
+            ),
+            ("178956971 years", "interval out of range"),
+        ] {
+            assert_eq!(from_json(s).unwrap_err().to_string(), error);
+        }
+        assert!(serde_json::from_str::<Interval>("42").is_err());
+    }
+
+    #[cfg(not(feature = "serde-struct"))]
+    #[test]
+    fn serialize_round_trips() {
+        for (interval, output) in INTERVAL_OUT {
+            let json = serde_json::to_string(interval).unwrap();
+            assert_eq!(json, format!("\"{}\"", output[3]));
+            let deserialized: Interval = serde_json::from_str(&json).unwrap();
+            assert!(deserialized.is_identical(interval), "{json}");
+        }
+    }
+
+    #[cfg(feature = "serde-struct")]
+    #[test]
+    fn serde_struct() {
+        let interval = Interval::new(-14, 3, -14_706_000_000);
+        let json = serde_json::to_string(&interval).unwrap();
+        assert_eq!(
+            json,
+            r#"{"months":-14,"days":3,"microseconds":-14706000000}"#
+        );
+        let deserialized: Interval = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.is_identical(&interval));
+    }
+
+    #[cfg(all(feature = "ts-rs", not(feature = "serde-struct")))]
+    #[test]
+    fn ts_rs_strings() {
+        use ts_rs::TS;
+
+        use crate::{DayTimeInterval, YearMonthInterval};
+
+        let inline = r#"`P${string}` | "infinity" | "-infinity""#;
+        let decl = format!("type Interval = {inline};");
+        assert_eq!(Interval::inline(), inline);
+        assert_eq!(Interval::decl(), decl);
+        assert_eq!(Interval::decl_concrete(), decl);
+        let export = Interval::export_to_string().unwrap();
+        assert!(export.ends_with(&format!("\nexport {decl}\n")), "{export}");
+        // qualified intervals serialize the same strings
+        assert_eq!(DayTimeInterval::<3>::name(), "Interval");
+        assert_eq!(DayTimeInterval::<3>::decl(), decl);
+        assert_eq!(YearMonthInterval::inline(), inline);
+    }
+
+    #[cfg(all(feature = "ts-rs", not(feature = "serde-struct")))]
+    #[test]
+    #[should_panic(expected = "Interval cannot be flattened")]
+    fn ts_rs_strings_cannot_be_flattened() {
+        <Interval as ts_rs::TS>::inline_flattened();
+    }
+
+    #[cfg(all(feature = "ts-rs", feature = "serde-struct"))]
+    #[test]
+    fn ts_rs_struct() {
+        use ts_rs::TS;
+
+        use crate::DayTimeInterval;
+
+        let inline = "{ months: number, days: number, microseconds: bigint, }";
+        assert_eq!(Interval::inline(), inline);
+        assert_eq!(Interval::inline_flattened(), inline);
+        assert_eq!(Interval::decl(), format!("type Interval = {inline};"));
+        assert_eq!(DayTimeInterval::<3>::decl(), Interval::decl());
+    }
+}
@@ -0,0 +1,413 @@
+//! Port of PostgreSQL's `make_interval` (`src/backend/utils/adt/timestamp.c`),
+//! and of `interval2itm`/`itm2interval` for editing an interval's fields.
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    Interval, MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_HOUR, USECS_PER_MINUTE, USECS_PER_SEC,
+    ops::f64_to_i64,
+};
+
+/// `DAYS_PER_WEEK`
+const DAYS_PER_WEEK: i32 = 7;
+
+/// An interval broken down the way PostgreSQL prints it (`struct pg_itm`):
+/// `1 year 2 mons 3 days 04:05:06.5` is 1 year, 2 months, 3 days, 4 hours,
+/// 5 minutes, 6 seconds and 500000 microseconds.
+///
+/// Every field has the sign of the interval field it comes from, so
+/// `-1 mons +02:00:00` has `months: -1` and `hours: 2`, and `-00:00:01.5`
+/// has `seconds: -1` and `micros: -500000`. Created by
+/// [`Interval::components`]; [`IntervalComponents::to_interval`] goes back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct IntervalComponents {
+    pub years: i32,
+    /// The months left over after whole years, `-11` to `11`
+    pub months: i32,
+    pub days: i32,
+    /// The hours of the time, which may exceed 24
+    pub hours: i64,
+    pub minutes: i32,
+    pub seconds: i32,
+    /// The fraction of the seconds, `-999999` to `999999`
+    pub micros: i32,
+}
+
+impl IntervalComponents {
+    /// `itm2interval`: put the fields back together, which needn't be in
+    /// their usual ranges (`90 minutes` is `01:30:00`), failing with
+    /// `interval out of range` if the months or the time overflow or the
+    /// result is one of the infinities.
+    pub fn to_interval(&self) -> Result<Interval, BoxDynError> {
+        let months =
+            i32::try_from(i64::from(self.years) * MONTHS_PER_YEAR + i64::from(self.months))
+                .map_err(|_| OUT_OF_RANGE)?;
+        let microseconds = self
+            .hours
+            .checked_mul(USECS_PER_HOUR)
+            .and_then(|time| time.checked_add(i64::from(self.minutes) * USECS_PER_MINUTE))
+            .and_then(|time| time.checked_add(i64::from(self.seconds) * USECS_PER_SEC))
+            .and_then(|time| time.checked_add(self.micros.into()))
+            .ok_or(OUT_OF_RANGE)?;
+        let interval = Interval {
+            months,
+            days: self.days,
+            microseconds,
+        };
+        if !interval.is_finite() {
+            return Err(OUT_OF_RANGE.into());
+        }
+        Ok(interval)
+    }
+}
+
+/// Builds an [`Interval`] from named parts like
+/// `make_interval(weeks => 2, hours => 36)`, created by
+/// [`Interval::builder`]: `Interval::builder().weeks(2).hours(36).build()` is
+/// `14 days 36:00:00`. Parts left unset are zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IntervalBuilder {
+    years: i32,
+    months: i32,
+    weeks: i32,
+    days: i32,
+    hours: i32,
+    mins: i32,
+    secs: f64,
+}
+
+impl IntervalBuilder {
+    pub const fn years(mut self, years: i32) -> Self {
+        self.years = years;
+        self
+    }
+
+    pub const fn months(mut self, months: i32) -> Self {
+        self.months = months;
+        self
+    }
+
+    pub const fn weeks(mut self, weeks: i32) -> Self {
+        self.weeks = weeks;
+        self
+    }
+
+    pub const fn days(mut self, days: i32) -> Self {
+        self.days = days;
+        self
+    }
+
+    pub const fn hours(mut self, hours: i32) -> Self {
+        self.hours = hours;
+        self
+    }
+
+    pub const fn mins(mut self, mins: i32) -> Self {
+        self.mins = mins;
+        self
+    }
+
+    /// The seconds, rounded to microseconds like `make_interval` does.
+    pub const fn secs(mut self, secs: f64) -> Self {
+        self.secs = secs;
+        self
+    }
+
+    /// [`Interval::make`] with the parts set so far.
+    pub fn build(&self) -> Result<Interval, BoxDynError> {
+        Interval::make(
+            self.years,
+            self.months,
+            self.weeks,
+            self.days,
+            self.hours,
+            self.mins,
+            self.secs,
+        )
+    }
+}
+
+impl Interval {
+    /// `make_interval(years, months, weeks, days, hours, mins, secs)`: years
+    /// and months make up `months`, weeks and days `days`, and the rest the
+    /// time, with `secs` rounded half to even to whole microseconds.
+    ///
+    /// Like PostgreSQL 17, this fails with `interval out of range` when a
+    /// field overflows, `secs` isn't finite, or the result is one of the
+    /// infinities, and with `value out of range: overflow` when `secs` is too
+    /// large to convert to microseconds at all.
+    pub fn make(
+        years: i32,
+        months: i32,
+        weeks: i32,
+        days: i32,
+        hours: i32,
+        mins: i32,
+        secs: f64,
+    ) -> Result<Self, BoxDynError> {
+        if !secs.is_finite() {
+            return Err(OUT_OF_RANGE.into());
+        }
+
+        let months = years
+            .checked_mul(MONTHS_PER_YEAR as i32)
+            .and_then(|m| m.checked_add(months))
+            .ok_or(OUT_OF_RANGE)?;
+        let days = weeks
+            .checked_mul(DAYS_PER_WEEK)
+            .and_then(|d| d.checked_add(days))
+            .ok_or(OUT_OF_RANGE)?;
+
+        // cannot overflow 64 bits
+        let time = i64::from(hours) * USECS_PER_HOUR + i64::from(mins) * USECS_PER_MINUTE;
+        // `float8_mul`
+        let usecs = secs * USECS_PER_SEC as f64;
+        if usecs.is_infinite() {
+            return Err("value out of range: overflow".into());
+        }
+        let microseconds = time
+            .checked_add(f64_to_i64(usecs.round_ties_even())?)
+            .ok_or(OUT_OF_RANGE)?;
+
+        let interval = Self {
+            months,
+            days,
+            microseconds,
+        };
+        if !interval.is_finite() {
+            return Err(OUT_OF_RANGE.into());
+        }
+        Ok(interval)
+    }
+
+    /// An [`IntervalBuilder`] with every part zero.
+    pub fn builder() -> IntervalBuilder {
+        IntervalBuilder::default()
+    }
+
+    /// The interval broken down into the fields PostgreSQL prints, e.g. for
+    /// filling a form; `None` for the infinities, which have none.
+    pub fn components(&self) -> Option<IntervalComponents> {
+        if !self.is_finite() {
+            return None;
+        }
+        let time = self.microseconds;
+        Some(IntervalComponents {
+            years: self.months / MONTHS_PER_YEAR as i32,
+            months: self.months % MONTHS_PER_YEAR as i32,
+            days: self.days,
+            hours: time / USECS_PER_HOUR,
+            minutes: (time % USECS_PER_HOUR / USECS_PER_MINUTE) as i32,
+            seconds: (time % USECS_PER_MINUTE / USECS_PER_SEC) as i32,
+            micros: (time % USECS_PER_SEC) as i32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, IntervalComponents};
+
+    #[test]
+    fn make_matches_make_interval() {
+        for (interval, expected) in [
+            (Interval::builder().years(2), "2 years"),
+            (Interval::builder().years(1).months(6), "1 year 6 mons"),
+            (
+                Interval::builder()
+                    .years(1)
+                    .months(-1)
+                    .weeks(5)
+                    .days(-7)
+                    .hours(25)
+                    .mins(-180),
+                "11 mons 28 days 22:00:00",
+            ),
+            (
+                Interval::builder().hours(-2).mins(-10).secs(-25.3),
+                "-02:10:25.3",
+            ),
+            (Interval::builder().secs(7e12), "1944444444:26:40"),
+            (Interval::builder().secs(0.000_000_5), "00:00:00"),
+            (Interval::builder().secs(0.000_001_5), "00:00:00.000002"),
+            (
+                Interval::builder().years(178_956_970).months(7),
+                "178956970 years 7 mons",
+            ),
+            (
+                Interval::builder().years(-178_956_970).months(-8),
+                "-178956970 years -8 mons",
+            ),
+            (
+                Interval::builder().weeks(306_783_378).days(1),
+                "2147483647 days",
+            ),
+        ] {
+            assert_eq!(interval.build().unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn make_overflow() {
+        // `interval.sql`'s "overflowing using make_interval" on PostgreSQL 17
+        for (interval, expected) in [
+            (
+                Interval::builder().years(178_956_971),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().years(-178_956_971),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().years(1).months(i32::MAX),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().years(-1).months(i32::MIN),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().weeks(306_783_379),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().weeks(-306_783_379),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().weeks(1).days(i32::MAX),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().weeks(-1).days(i32::MIN),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().secs(1e308),
+                "value out of range: overflow",
+            ),
+            (Interval::builder().secs(1e18), "interval out of range"),
+            (Interval::builder().secs(-1e18), "interval out of range"),
+            (
+                Interval::builder().mins(1).secs(9_223_372_036_800.0),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().mins(-1).secs(-9_223_372_036_800.0),
+                "interval out of range",
+            ),
+            // `secs` must be finite
+            (
+                Interval::builder().secs(f64::INFINITY),
+                "interval out of range",
+            ),
+            (
+                Interval::builder().secs(f64::NEG_INFINITY),
+                "interval out of range",
+            ),
+            (Interval::builder().secs(f64::NAN), "interval out of range"),
+        ] {
+            let error = interval.build().unwrap_err();
+            assert_eq!(error.to_string(), expected, "{interval:?}");
+        }
+    }
+
+    #[test]
+    fn components_match_interval2itm() {
+        let parts = |years, months, days, hours, minutes, seconds, micros| IntervalComponents {
+            years,
+            months,
+            days,
+            hours,
+            minutes,
+            seconds,
+            micros,
+        };
+        for (input, expected) in [
+            (
+                "1 year 2 mons 3 days 04:05:06.5",
+                parts(1, 2, 3, 4, 5, 6, 500_000),
+            ),
+            ("-1 mons +02:00:00", parts(0, -1, 0, 2, 0, 0, 0)),
+            ("-00:00:01.5", parts(0, 0, 0, 0, 0, -1, -500_000)),
+            (
+                "-1 years -11 mons 1 day -00:30:00",
+                parts(-1, -11, 1, 0, -30, 0, 0),
+            ),
+            ("13 mons -40 days", parts(1, 1, -40, 0, 0, 0, 0)),
+            ("100:59:59.999999", parts(0, 0, 0, 100, 59, 59, 999_999)),
+            (
+                "-2562047788:00:54.775807",
+                parts(0, 0, 0, -2_562_047_788, 0, -54, -775_807),
+            ),
+            (
+                "-178956970 years -8 mons",
+                parts(-178_956_970, -8, 0, 0, 0, 0, 0),
+            ),
+        ] {
+            let interval: Interval = input.parse().unwrap();
+            let components = interval.components().unwrap();
+            assert_eq!(components, expected, "{input}");
+            let round_trip = components.to_interval().unwrap();
+            assert!(round_trip.is_identical(&interval), "{input}");
+        }
+        assert_eq!(Interval::INFINITY.components(), None);
+        assert_eq!(Interval::NEG_INFINITY.components(), None);
+    }
+
+    #[test]
+    fn to_interval_normalizes_and_overflows() {
+        let parts = IntervalComponents {
+            months: 14,
+            minutes: 90,
+            seconds: -1,
+            micros: 1_500_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            parts.to_interval().unwrap().to_string(),
+            "1 year 2 mons 01:30:00.5"
+        );
+
+        for parts in [
+            IntervalComponents {
+                years: 178_956_970,
+                months: 8,
+                ..Default::default()
+            },
+            IntervalComponents {
+                years: i32::MIN,
+                ..Default::default()
+            },
+            IntervalComponents {
+                hours: 2_562_047_789,
+                ..Default::default()
+            },
+            IntervalComponents {
+                hours: i64::MIN,
+                ..Default::default()
+            },
+            IntervalComponents {
+                hours: 2_562_047_788,
+                minutes: 0,
+                seconds: 54,
+                micros: 775_808,
+                ..Default::default()
+            },
+            // the fields reserved for the infinities
+            IntervalComponents {
+                years: 178_956_970,
+                months: 7,
+                days: i32::MAX,
+                hours: 2_562_047_788,
+                minutes: 0,
+                seconds: 54,
+                micros: 775_807,
+            },
+        ] {
+            let error = parts.to_interval().unwrap_err();
+            assert_eq!(error.to_string(), "interval out of range", "{parts:?}");
+        }
+    }
+}
@@ -0,0 +1,975 @@
+//! Port of PostgreSQL's interval arithmetic (`interval_pl`, `interval_mi`,
+//! `interval_um`, `interval_mul` and `interval_div` in
+//! `src/backend/utils/adt/timestamp.c`).
+
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use sqlx::error::BoxDynError;
+
+use crate::{DAYS_PER_MONTH, Interval, OUT_OF_RANGE, USECS_PER_SEC};
+
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// `TSROUND`: round to the 6 fractional digits a timestamp can hold.
+fn ts_round(value: f64) -> f64 {
+    (value * 1_000_000.0).round_ties_even() / 1_000_000.0
+}
+
+/// `FLOAT8_FITS_IN_INT32`
+fn f64_to_i32(value: f64) -> Result<i32, BoxDynError> {
+    if value >= f64::from(i32::MIN) && value < -f64::from(i32::MIN) {
+        Ok(value as i32)
+    } else {
+        Err(OUT_OF_RANGE.into())
+    }
+}
+
+/// Reject a finite result that landed on the fields reserved for
+/// [`Interval::INFINITY`] or [`Interval::NEG_INFINITY`].
+fn finite(interval: Interval) -> Result<Interval, BoxDynError> {
+    if interval.is_finite() {
+        Ok(interval)
+    } else {
+        Err(OUT_OF_RANGE.into())
+    }
+}
+
+/// `FLOAT8_FITS_IN_INT64`
+pub(crate) fn f64_to_i64(value: f64) -> Result<i64, BoxDynError> {
+    if value >= i64::MIN as f64 && value < -(i64::MIN as f64) {
+        Ok(value as i64)
+    } else {
+        Err(OUT_OF_RANGE.into())
+    }
+}
+
+impl Interval {
+    /// `interval_pl`: add the intervals field by field, failing with
+    /// `interval out of range` if `months`, `days` or `microseconds`
+    /// overflows.
+    ///
+    /// An infinite operand makes the sum that infinity, and adding opposite
+    /// infinities fails.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, BoxDynError> {
+        if (self.is_infinity() && other.is_neg_infinity())
+            || (self.is_neg_infinity() && other.is_infinity())
+        {
+            return Err(OUT_OF_RANGE.into());
+        }
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        if !other.is_finite() {
+            return Ok(other.clone());
+        }
+        finite(Self {
+            months: self.months.checked_add(other.months).ok_or(OUT_OF_RANGE)?,
+            days: self.days.checked_add(other.days).ok_or(OUT_OF_RANGE)?,
+            microseconds: self
+                .microseconds
+                .checked_add(other.microseconds)
+                .ok_or(OUT_OF_RANGE)?,
+        })
+    }
+
+    /// `interval_mi`: subtract the intervals field by field, failing with
+    /// `interval out of range` if `months`, `days` or `microseconds`
+    /// overflows.
+    ///
+    /// An infinite `self` stays as it is, a finite `self` minus an infinity
+    /// is the opposite infinity, and subtracting an infinity from itself
+    /// fails.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, BoxDynError> {
+        if (self.is_infinity() && other.is_infinity())
+            || (self.is_neg_infinity() && other.is_neg_infinity())
+        {
+            return Err(OUT_OF_RANGE.into());
+        }
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        if !other.is_finite() {
+            return other.checked_neg();
+        }
+        finite(Self {
+            months: self.months.checked_sub(other.months).ok_or(OUT_OF_RANGE)?,
+            days: self.days.checked_sub(other.days).ok_or(OUT_OF_RANGE)?,
+            microseconds: self
+                .microseconds
+                .checked_sub(other.microseconds)
+                .ok_or(OUT_OF_RANGE)?,
+        })
+    }
+
+    /// `interval_um`: negate every field, failing with `interval out of range`
+    /// if one of them is its type's minimum. The infinities swap.
+    pub fn checked_neg(&self) -> Result<Self, BoxDynError> {
+        if self.is_infinity() {
+            return Ok(Self::NEG_INFINITY);
+        }
+        if self.is_neg_infinity() {
+            return Ok(Self::INFINITY);
+        }
+        finite(Self {
+            months: self.months.checked_neg().ok_or(OUT_OF_RANGE)?,
+            days: self.days.checked_neg().ok_or(OUT_OF_RANGE)?,
+            microseconds: self.microseconds.checked_neg().ok_or(OUT_OF_RANGE)?,
+        })
+    }
+
+    /// `interval_mul`: multiply by `factor` like `interval * float8`.
+    ///
+    /// Fractional months spill into days and fractional days into
+    /// microseconds (never upwards), rounded the way the server rounds them,
+    /// so `1 mon` times `1.5` is `1 mon 15 days`.
+    ///
+    /// An infinity times a positive or negative factor is that infinity or
+    /// its opposite, and a finite nonzero interval times an infinite factor is
+    /// the infinity of the product's sign. Multiplying an infinity by zero,
+    /// zero by an infinity, or anything by NaN fails.
+    pub fn checked_mul_f64(&self, factor: f64) -> Result<Self, BoxDynError> {
+        if factor.is_nan() {
+            return Err(OUT_OF_RANGE.into());
+        }
+        if !self.is_finite() {
+            return self.scale_infinite(factor == 0.0, factor < 0.0);
+        }
+        if factor.is_infinite() {
+            // `interval_sign`
+            let zero = Self {
+                months: 0,
+                days: 0,
+                microseconds: 0,
+            };
+            let sign = match self.cmp(&zero) {
+                Ordering::Less => -1.0,
+                Ordering::Equal => return Err(OUT_OF_RANGE.into()),
+                Ordering::Greater => 1.0,
+            };
+            return Ok(if factor * sign < 0.0 {
+                Self::NEG_INFINITY
+            } else {
+                Self::INFINITY
+            });
+        }
+        self.cascade_f64(|value| value * factor)
+    }
+
+    /// `interval_div`: divide by `factor` like `interval / float8`, with the
+    /// same cascade of fractions as [`Interval::checked_mul_f64`].
+    ///
+    /// An infinity divided by a finite factor keeps or flips its sign with
+    /// the factor's, and a finite interval divided by an infinite factor is
+    /// zero. Dividing an infinity by an infinity fails.
+    pub fn checked_div_f64(&self, factor: f64) -> Result<Self, BoxDynError> {
+        if factor == 0.0 {
+            return Err("division by zero".into());
+        }
+        if factor.is_nan() {
+            return Err(OUT_OF_RANGE.into());
+        }
+        if !self.is_finite() {
+            return self.scale_infinite(factor.is_infinite(), factor < 0.0);
+        }
+        if factor.is_infinite() {
+            return Ok(Self {
+                months: 0,
+                days: 0,
+                microseconds: 0,
+            });
+        }
+        self.cascade_f64(|value| value / factor)
+    }
+
+    /// Scale an infinite interval: fail if `invalid`, otherwise keep it or,
+    /// if `negative`, flip it.
+    fn scale_infinite(&self, invalid: bool, negative: bool) -> Result<Self, BoxDynError> {
+        if invalid {
+            Err(OUT_OF_RANGE.into())
+        } else if negative {
+            self.checked_neg()
+        } else {
+            Ok(self.clone())
+        }
+    }
+
+    /// The body shared by `interval_mul` and `interval_div`, with `scale`
+    /// applying the factor to one field.
+    fn cascade_f64(&self, scale: impl Fn(f64) -> f64) -> Result<Self, BoxDynError> {
+        let months = f64_to_i32(scale(f64::from(self.months)))?;
+        let mut days = f64_to_i32(scale(f64::from(self.days)))?;
+
+        // fractional months full days into days, the rest into seconds
+        let month_remainder_days = ts_round(
+            (scale(f64::from(self.months)) - f64::from(months)) * f64::from(DAYS_PER_MONTH),
+        );
+        let mut sec_remainder = ts_round(
+            (scale(f64::from(self.days)) - f64::from(days) + month_remainder_days
+                - f64::from(month_remainder_days as i32))
+                * SECS_PER_DAY,
+        );
+
+        // might be 24:00:00 from rounding, or more from both cascades
+        if sec_remainder.abs() >= SECS_PER_DAY {
+            let whole_days = (sec_remainder / SECS_PER_DAY) as i32;
+            days = days.checked_add(whole_days).ok_or(OUT_OF_RANGE)?;
+            sec_remainder -= f64::from(whole_days) * SECS_PER_DAY;
+        }
+
+        days = days
+            .checked_add(month_remainder_days as i32)
+            .ok_or(OUT_OF_RANGE)?;
+        let microseconds = f64_to_i64(
+            (scale(self.microseconds as f64) + sec_remainder * USECS_PER_SEC as f64)
+                .round_ties_even(),
+        )?;
+
+        finite(Self {
+            months,
+            days,
+            microseconds,
+        })
+    }
+
+    /// Multiply by `factor` with the cascade of [`Interval::checked_mul_f64`]
+    /// carried out in exact decimal arithmetic, so factors like `1.1` have no
+    /// binary rounding error. Microseconds are rounded half to even, as the
+    /// server does. Infinities follow the rules of
+    /// [`Interval::checked_mul_f64`].
+    #[cfg(feature = "rust_decimal")]
+    pub fn checked_mul_decimal(&self, factor: rust_decimal::Decimal) -> Result<Self, BoxDynError> {
+        use rust_decimal::{Decimal, RoundingStrategy::MidpointNearestEven, prelude::ToPrimitive};
+
+        if !self.is_finite() {
+            return self.scale_infinite(factor.is_zero(), factor.is_sign_negative());
+        }
+
+        let mul = |value: Decimal| value.checked_mul(factor).ok_or(OUT_OF_RANGE);
+        let round_usecs = |value: Decimal| value.round_dp_with_strategy(6, MidpointNearestEven);
+        let secs_per_day = Decimal::from(86_400);
+
+        let scaled_months = mul(self.months.into())?;
+        let scaled_days = mul(self.days.into())?;
+        let months = scaled_months.trunc().to_i32().ok_or(OUT_OF_RANGE)?;
+        let mut days = scaled_days.trunc().to_i32().ok_or(OUT_OF_RANGE)?;
+
+        let month_remainder_days =
+            round_usecs((scaled_months - Decimal::from(months)) * Decimal::from(DAYS_PER_MONTH));
+        let mut sec_remainder = round_usecs(
+            (scaled_days - Decimal::from(days) + month_remainder_days.fract()) * secs_per_day,
+        );
+
+        if sec_remainder.abs() >= secs_per_day {
+            let whole_days = (sec_remainder / secs_per_day).trunc();
+            days = days
+                .checked_add(whole_days.to_i32().ok_or(OUT_OF_RANGE)?)
+                .ok_or(OUT_OF_RANGE)?;
+            sec_remainder -= whole_days * secs_per_day;
+        }
+
+        days = days
+            .checked_add(month_remainder_days.trunc().to_i32().ok_or(OUT_OF_RANGE)?)
+            .ok_or(OUT_OF_RANGE)?;
+        let microseconds = sec_remainder
+            .checked_mul(Decimal::from(USECS_PER_SEC))
+            .and_then(|remainder| mul(self.microseconds.into()).ok()?.checked_add(remainder))
+            .ok_or(OUT_OF_RANGE)?
+            .round_dp_with_strategy(0, MidpointNearestEven)
+            .to_i64()
+            .ok_or(OUT_OF_RANGE)?;
+
+        finite(Self {
+            months,
+            days,
+            microseconds,
+        })
+    }
+
+    /// Add the intervals field by field, clamping each field to its type's
+    /// range instead of overflowing. A sum clamped in every field is the
+    /// infinity in that direction.
+    ///
+    /// Infinities follow [`Interval::checked_add`], except that the sum of
+    /// opposite infinities, which has no value, is `self`.
+    pub fn saturating_add(&self, other: &Self) -> Self {
+        if !self.is_finite() {
+            return self.clone();
+        }
+        if !other.is_finite() {
+            return other.clone();
+        }
+        Self {
+            months: self.months.saturating_add(other.months),
+            days: self.days.saturating_add(other.days),
+            microseconds: self.microseconds.saturating_add(other.microseconds),
+        }
+    }
+
+    /// Subtract the intervals field by field, clamping each field to its
+    /// type's range instead of overflowing. A difference clamped in every
+    /// field is the infinity in that direction.
+    ///
+    /// Infinities follow [`Interval::checked_sub`], except that an infinity
+    /// minus itself, which has no value, is `self`.
+    pub fn saturating_sub(&self, other: &Self) -> Self {
+        if !self.is_finite() {
+            return self.clone();
+        }
+        if !other.is_finite() {
+            return other.saturating_neg();
+        }
+        Self {
+            months: self.months.saturating_sub(other.months),
+            days: self.days.saturating_sub(other.days),
+            microseconds: self.microseconds.saturating_sub(other.microseconds),
+        }
+    }
+
+    /// Negate every field, clamping a field at its type's minimum to its
+    /// maximum. The infinities swap.
+    pub fn saturating_neg(&self) -> Self {
+        if self.is_infinity() {
+            return Self::NEG_INFINITY;
+        }
+        if self.is_neg_infinity() {
+            return Self::INFINITY;
+        }
+        Self {
+            months: self.months.saturating_neg(),
+            days: self.days.saturating_neg(),
+            microseconds: self.microseconds.saturating_neg(),
+        }
+    }
+}
+
+impl Add<&Interval> for &Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_add`].
+    fn add(self, rhs: &Interval) -> Interval {
+        self.checked_add(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_add`].
+    fn add(self, rhs: Interval) -> Interval {
+        &self + &rhs
+    }
+}
+
+impl Sub<&Interval> for &Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_sub`].
+    fn sub(self, rhs: &Interval) -> Interval {
+        self.checked_sub(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_sub`].
+    fn sub(self, rhs: Interval) -> Interval {
+        &self - &rhs
+    }
+}
+
+impl Neg for &Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_neg`].
+    fn neg(self) -> Interval {
+        self.checked_neg().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_neg`].
+    fn neg(self) -> Interval {
+        -&self
+    }
+}
+
+impl AddAssign<&Interval> for Interval {
+    fn add_assign(&mut self, rhs: &Interval) {
+        *self = &*self + rhs;
+    }
+}
+
+impl AddAssign for Interval {
+    fn add_assign(&mut self, rhs: Interval) {
+        *self += &rhs;
+    }
+}
+
+impl SubAssign<&Interval> for Interval {
+    fn sub_assign(&mut self, rhs: &Interval) {
+        *self = &*self - rhs;
+    }
+}
+
+impl SubAssign for Interval {
+    fn sub_assign(&mut self, rhs: Interval) {
+        *self -= &rhs;
+    }
+}
+
+impl Mul<i32> for &Interval {
+    type Output = Interval;
+
+    /// `interval * integer`, which the server computes as `interval * float8`.
+    ///
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_mul_f64`].
+    fn mul(self, rhs: i32) -> Interval {
+        self.checked_mul_f64(rhs.into())
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Mul<i32> for Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_mul_f64`].
+    fn mul(self, rhs: i32) -> Interval {
+        &self * rhs
+    }
+}
+
+impl Mul<i64> for &Interval {
+    type Output = Interval;
+
+    /// `interval * bigint`, which the server computes as `interval * float8`.
+    ///
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_mul_f64`].
+    fn mul(self, rhs: i64) -> Interval {
+        self.checked_mul_f64(rhs as f64)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Mul<i64> for Interval {
+    type Output = Interval;
+
+    /// # Panics
+    ///
+    /// On overflow; see [`Interval::checked_mul_f64`].
+    fn mul(self, rhs: i64) -> Interval {
+        &self * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::error::BoxDynError;
+
+    use crate::Interval;
+
+    fn check(actual: Result<Interval, BoxDynError>, expected: Result<&str, &str>) {
+        let actual = actual
+            .map(|interval| interval.to_string())
+            .map_err(|e| e.to_string());
+        assert_eq!(actual, expected.map(str::to_owned).map_err(str::to_owned));
+    }
+
+    /// `interval * float8` and `interval / float8` on PostgreSQL.
+    const MUL_DIV: &[(&str, &str, f64, Result<&str, &str>)] = &[
+        // `INTERVAL_MULDIV_TBL` times 0.3 and 8.2 and divided by 10 and 100
+        (
+            "41 mons 12 days 360:00:00",
+            "*",
+            0.3,
+            Ok("1 year 12 days 122:24:00"),
+        ),
+        (
+            "41 mons 12 days 360:00:00",
+            "*",
+            8.2,
+            Ok("28 years 104 days 2961:36:00"),
+        ),
+        (
+            "41 mons 12 days 360:00:00",
+            "/",
+            10.0,
+            Ok("4 mons 4 days 40:48:00"),
+        ),
+        (
+            "41 mons 12 days 360:00:00",
+            "/",
+            100.0,
+            Ok("12 days 13:40:48"),
+        ),
+        (
+            "-41 mons -12 days 360:00:00",
+            "*",
+            0.3,
+            Ok("-1 years -12 days +93:36:00"),
+        ),
+        (
+            "-41 mons -12 days 360:00:00",
+            "*",
+            8.2,
+            Ok("-28 years -104 days +2942:24:00"),
+        ),
+        (
+            "-41 mons -12 days 360:00:00",
+            "/",
+            10.0,
+            Ok("-4 mons -4 days +31:12:00"),
+        ),
+        (
+            "-41 mons -12 days 360:00:00",
+            "/",
+            100.0,
+            Ok("-12 days -06:28:48"),
+        ),
+        ("-12 days", "*", 0.3, Ok("-3 days -14:24:00")),
+        ("-12 days", "*", 8.2, Ok("-98 days -09:36:00")),
+        ("-12 days", "/", 10.0, Ok("-1 days -04:48:00")),
+        ("-12 days", "/", 100.0, Ok("-02:52:48")),
+        (
+            "9 mons -27 days 12:34:56",
+            "*",
+            0.3,
+            Ok("2 mons 13 days 01:22:28.8"),
+        ),
+        (
+            "9 mons -27 days 12:34:56",
+            "*",
+            8.2,
+            Ok("6 years 1 mon -197 days +93:34:27.2"),
+        ),
+        (
+            "9 mons -27 days 12:34:56",
+            "/",
+            10.0,
+            Ok("25 days -15:32:30.4"),
+        ),
+        (
+            "9 mons -27 days 12:34:56",
+            "/",
+            100.0,
+            Ok("2 days 10:26:44.96"),
+        ),
+        (
+            "-3 years 482 days 76:54:32.189",
+            "*",
+            0.3,
+            Ok("-10 mons +120 days 37:28:21.6567"),
+        ),
+        (
+            "-3 years 482 days 76:54:32.189",
+            "*",
+            8.2,
+            Ok("-24 years -7 mons +3946 days 640:15:11.9498"),
+        ),
+        (
+            "-3 years 482 days 76:54:32.189",
+            "/",
+            10.0,
+            Ok("-3 mons +30 days 12:29:27.2189"),
+        ),
+        (
+            "-3 years 482 days 76:54:32.189",
+            "/",
+            100.0,
+            Ok("-6 days +01:14:56.72189"),
+        ),
+        ("4 mons", "*", 0.3, Ok("1 mon 6 days")),
+        ("4 mons", "*", 8.2, Ok("2 years 8 mons 24 days")),
+        ("4 mons", "/", 10.0, Ok("12 days")),
+        ("4 mons", "/", 100.0, Ok("1 day 04:48:00")),
+        ("14 mons", "*", 0.3, Ok("4 mons 6 days")),
+        ("14 mons", "*", 8.2, Ok("9 years 6 mons 24 days")),
+        ("14 mons", "/", 10.0, Ok("1 mon 12 days")),
+        ("14 mons", "/", 100.0, Ok("4 days 04:48:00")),
+        (
+            "999 mons 999 days",
+            "*",
+            0.3,
+            Ok("24 years 11 mons 320 days 16:48:00"),
+        ),
+        (
+            "999 mons 999 days",
+            "*",
+            8.2,
+            Ok("682 years 7 mons 8215 days 19:12:00"),
+        ),
+        (
+            "999 mons 999 days",
+            "/",
+            10.0,
+            Ok("8 years 3 mons 126 days 21:36:00"),
+        ),
+        (
+            "999 mons 999 days",
+            "/",
+            100.0,
+            Ok("9 mons 39 days 16:33:36"),
+        ),
+        // fractions cascade into days and microseconds, never upwards
+        ("1 mon", "*", 1.5, Ok("1 mon 15 days")),
+        ("1 mon", "/", 2.0, Ok("15 days")),
+        ("1 day", "*", 1.5, Ok("1 day 12:00:00")),
+        ("1 day", "/", 3.0, Ok("08:00:00")),
+        ("01:00:00", "*", 0.5, Ok("00:30:00")),
+        ("1 mon", "*", -1.5, Ok("-1 mons -15 days")),
+        // division by zero, and the `int32` and `int64` boundaries
+        ("1 day", "/", 0.0, Err("division by zero")),
+        ("0", "*", 1e300, Ok("00:00:00")),
+        ("2147483647 mons", "*", 1.0, Ok("178956970 years 7 mons")),
+        (
+            "2147483647 mons",
+            "*",
+            1.0000001,
+            Err("interval out of range"),
+        ),
+        ("-2147483648 mons", "*", 1.0, Ok("-178956970 years -8 mons")),
+        ("-2147483648 mons", "*", -1.0, Err("interval out of range")),
+        ("-2147483648 mons", "/", -1.0, Err("interval out of range")),
+        ("2147483647 days", "*", 1.0, Ok("2147483647 days")),
+        ("2147483647 days", "*", 2.0, Err("interval out of range")),
+        ("1 day", "*", 2147483647.0, Ok("2147483647 days")),
+        ("1 day", "*", 2147483648.0, Err("interval out of range")),
+        (
+            "2562047788:00:54.775807",
+            "*",
+            1.0,
+            Err("interval out of range"),
+        ),
+        (
+            "2562047788:00:54.775807",
+            "*",
+            0.5,
+            Ok("1281023894:00:27.387904"),
+        ),
+        ("1 hour", "*", 2562047789.0, Err("interval out of range")),
+        ("1 hour", "*", 2562047787.0, Ok("2562047787:00:00")),
+        ("1 mon", "*", 1e-7, Ok("00:00:00.2592")),
+        ("1 mon", "*", 71582788.3, Ok("5965232 years 4 mons 9 days")),
+        (
+            "29 days 23:59:59.999999",
+            "*",
+            1.0,
+            Ok("29 days 23:59:59.999999"),
+        ),
+        ("1 day", "*", f64::NAN, Err("interval out of range")),
+        ("1 day", "/", f64::NAN, Err("interval out of range")),
+    ];
+
+    #[test]
+    fn mul_and_div_match_postgres() {
+        for &(interval, op, factor, expected) in MUL_DIV {
+            let interval: Interval = interval.parse().unwrap();
+            let actual = match op {
+                "*" => interval.checked_mul_f64(factor),
+                _ => interval.checked_div_f64(factor),
+            };
+            check(actual, expected);
+        }
+    }
+
+    #[test]
+    fn add_and_sub_with_infinities() {
+        // PostgreSQL 17's `interval_pl` and `interval_mi`
+        for (lhs, op, rhs, expected) in [
+            ("infinity", "+", "infinity", Ok("infinity")),
+            ("infinity", "+", "-infinity", Err("interval out of range")),
+            ("-infinity", "+", "infinity", Err("interval out of range")),
+            ("-infinity", "+", "-infinity", Ok("-infinity")),
+            ("infinity", "+", "1 day", Ok("infinity")),
+            ("1 day", "+", "-infinity", Ok("-infinity")),
+            ("infinity", "-", "infinity", Err("interval out of range")),
+            ("-infinity", "-", "-infinity", Err("interval out of range")),
+            ("infinity", "-", "-infinity", Ok("infinity")),
+            ("-infinity", "-", "infinity", Ok("-infinity")),
+            ("-infinity", "-", "1 day", Ok("-infinity")),
+            ("1 day", "-", "infinity", Ok("-infinity")),
+            ("1 day", "-", "-infinity", Ok("infinity")),
+        ] {
+            let (lhs, rhs): (Interval, Interval) = (lhs.parse().unwrap(), rhs.parse().unwrap());
+            let actual = match op {
+                "+" => lhs.checked_add(&rhs),
+                _ => lhs.checked_sub(&rhs),
+            };
+            check(actual, expected);
+        }
+    }
+
+    #[test]
+    fn finite_results_cannot_become_infinite() {
+        // results landing exactly on the fields reserved for the infinities
+        let almost = Interval::new(i32::MIN, i32::MIN, -1);
+        let rest = Interval::new(0, 0, i64::MIN + 1);
+        check(almost.checked_add(&rest), Err("interval out of range"));
+        let max = Interval::new(0, 0, i64::MAX);
+        check(almost.checked_sub(&max), Err("interval out of range"));
+        let almost = Interval::new(i32::MAX, i32::MAX, 1);
+        let rest = Interval::new(0, 0, i64::MAX - 1);
+        check(almost.checked_add(&rest), Err("interval out of range"));
+        let max = Interval::new(i32::MAX, i32::MAX, i64::MAX);
+        check(
+            almost.checked_sub(&Interval::new(0, 0, -i64::MAX + 1)),
+            Err("interval out of range"),
+        );
+        check(Interval::new(0, 0, 1).checked_add(&max), Ok("infinity"));
+        let almost = Interval::new(-i32::MAX, -i32::MAX, -i64::MAX);
+        check(almost.checked_neg(), Err("interval out of range"));
+        check(Interval::INFINITY.checked_neg(), Ok("-infinity"));
+        check(Interval::NEG_INFINITY.checked_neg(), Ok("infinity"));
+    }
+
+    #[test]
+    fn field_overflow() {
+        const OUT_OF_RANGE: Result<&str, &str> = Err("interval out of range");
+        for (lhs, rhs) in [
+            (Interval::new(i32::MAX, 0, 0), Interval::new(1, 0, 0)),
+            (Interval::new(0, i32::MAX, 0), Interval::new(0, 1, 0)),
+            (Interval::new(0, 0, i64::MAX), Interval::new(0, 0, 1)),
+            (Interval::new(i32::MIN, 0, 0), Interval::new(-1, 0, 0)),
+            (Interval::new(0, i32::MIN, 0), Interval::new(0, -1, 0)),
+            (Interval::new(0, 0, i64::MIN), Interval::new(0, 0, -1)),
+            // one field overflowing fails the others
+            (Interval::new(1, i32::MAX, 1), Interval::new(1, 1, 1)),
+        ] {
+            check(lhs.checked_add(&rhs), OUT_OF_RANGE);
+            check(lhs.checked_sub(&rhs.checked_neg().unwrap()), OUT_OF_RANGE);
+        }
+        check(Interval::new(i32::MIN, 0, 0).checked_neg(), OUT_OF_RANGE);
+        check(Interval::new(0, i32::MIN, 0).checked_neg(), OUT_OF_RANGE);
+        check(Interval::new(0, 0, i64::MIN).checked_neg(), OUT_OF_RANGE);
+        check(
+            Interval::new(0, 0, i64::MIN).checked_sub(&Interval::new(0, 0, 1)),
+            OUT_OF_RANGE,
+        );
+        check(
+            Interval::new(0, 0, 0).checked_sub(&Interval::new(0, 0, i64::MIN)),
+            OUT_OF_RANGE,
+        );
+        check(
+            Interval::new(0, 0, -1).checked_sub(&Interval::new(0, 0, i64::MAX)),
+            Ok("-2562047788:00:54.775808"),
+        );
+        check(
+            Interval::new(i32::MAX - 1, 1, 0).checked_add(&Interval::new(1, -1, 0)),
+            Ok("178956970 years 7 mons"),
+        );
+    }
+
+    #[test]
+    fn saturating() {
+        let month = Interval::new(1, 0, 0);
+        let clamped = Interval::new(i32::MAX, 1, 2).saturating_add(&Interval::new(1, 1, 1));
+        assert!(clamped.is_identical(&Interval::new(i32::MAX, 2, 3)));
+        let clamped = Interval::new(i32::MIN, 0, 0).saturating_sub(&month);
+        assert!(clamped.is_identical(&Interval::new(i32::MIN, 0, 0)));
+        let clamped = Interval::new(0, 0, i64::MIN).saturating_neg();
+        assert!(clamped.is_identical(&Interval::new(0, 0, i64::MAX)));
+        let clamped = Interval::new(i32::MIN, 1, -1).saturating_neg();
+        assert!(clamped.is_identical(&Interval::new(i32::MAX, -1, 1)));
+
+        // clamped in every field is the infinity in that direction
+        let ones = Interval::new(1, 1, 1);
+        let max = Interval::new(i32::MAX - 1, i32::MAX, i64::MAX - 1);
+        assert!(max.saturating_add(&ones).is_infinity());
+        let min = Interval::new(i32::MIN, i32::MIN + 1, i64::MIN);
+        assert!(min.saturating_sub(&ones).is_neg_infinity());
+
+        assert!(
+            Interval::INFINITY
+                .saturating_add(&Interval::NEG_INFINITY)
+                .is_infinity()
+        );
+        assert!(
+            Interval::NEG_INFINITY
+                .saturating_add(&Interval::INFINITY)
+                .is_neg_infinity()
+        );
+        assert!(
+            Interval::INFINITY
+                .saturating_sub(&Interval::INFINITY)
+                .is_infinity()
+        );
+        assert!(
+            month
+                .saturating_add(&Interval::NEG_INFINITY)
+                .is_neg_infinity()
+        );
+        assert!(month.saturating_sub(&Interval::INFINITY).is_neg_infinity());
+        assert!(month.saturating_sub(&Interval::NEG_INFINITY).is_infinity());
+        assert!(Interval::INFINITY.saturating_neg().is_neg_infinity());
+        assert!(Interval::NEG_INFINITY.saturating_neg().is_infinity());
+    }
+
+    #[test]
+    fn operators() {
+        let (month, day) = (Interval::new(1, 0, 0), Interval::new(0, 1, 0));
+        assert_eq!((month.clone() + day.clone()).to_string(), "1 mon 1 day");
+        assert_eq!((&month - &day).to_string(), "1 mon -1 days");
+        assert_eq!((-&month).to_string(), "-1 mons");
+        let mut sum = month.clone();
+        sum += &day;
+        sum -= Interval::new(0, 0, 1);
+        assert_eq!(sum.to_string(), "1 mon 1 day -00:00:00.000001");
+        assert_eq!((Interval::INFINITY + month).to_string(), "infinity");
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn add_panics_on_overflow() {
+        let _ = Interval::new(i32::MAX, 0, 0) + Interval::new(1, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn add_panics_on_opposite_infinities() {
+        let _ = &Interval::INFINITY + &Interval::NEG_INFINITY;
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn sub_panics_on_overflow() {
+        let _ = Interval::new(0, i32::MIN, 0) - Interval::new(0, 1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn sub_assign_panics_on_overflow() {
+        let mut interval = Interval::new(0, 0, i64::MIN);
+        interval -= Interval::new(0, 0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval out of range")]
+    fn neg_panics_on_overflow() {
+        let _ = -Interval::new(0, 0, i64::MIN);
+    }
+
+    #[test]
+    fn mul_and_div_with_infinities() {
+        // PostgreSQL 17's `interval_mul` and `interval_div`
+        for (interval, op, factor, expected) in [
+            ("infinity", "*", 2.0, Ok("infinity")),
+            ("infinity", "*", -2.0, Ok("-infinity")),
+            ("-infinity", "*", -0.5, Ok("infinity")),
+            ("infinity", "*", f64::INFINITY, Ok("infinity")),
+            ("infinity", "*", 0.0, Err("interval out of range")),
+            ("-infinity", "*", 0.0, Err("interval out of range")),
+            ("infinity", "*", f64::NAN, Err("interval out of range")),
+            ("1 day", "*", f64::INFINITY, Ok("infinity")),
+            ("-1 day", "*", f64::INFINITY, Ok("-infinity")),
+            ("1 day", "*", f64::NEG_INFINITY, Ok("-infinity")),
+            ("0", "*", f64::INFINITY, Err("interval out of range")),
+            ("infinity", "/", 2.0, Ok("infinity")),
+            ("infinity", "/", -2.0, Ok("-infinity")),
+            ("infinity", "/", 0.0, Err("division by zero")),
+            ("infinity", "/", f64::INFINITY, Err("interval out of range")),
+            ("1 day", "/", f64::INFINITY, Ok("00:00:00")),
+        ] {
+            let interval: Interval = interval.parse().unwrap();
+            let actual = match op {
+                "*" => interval.checked_mul_f64(factor),
+                _ => interval.checked_div_f64(factor),
+            };
+            check(actual, expected);
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn mul_decimal() {
+        use rust_decimal::Decimal;
+
+        // `interval * float8` rounds microseconds beyond 2^53 to an `f64`
+        let exact = "2562047788:00:54.775807";
+        for &(interval, op, factor, expected) in MUL_DIV {
+            // `0 * 1e300` is beyond `Decimal`
+            let (Ok(factor), "*") = (Decimal::try_from(factor), op) else {
+                continue;
+            };
+            let expected = if (interval, factor) == (exact, Decimal::ONE) {
+                Ok(exact)
+            } else {
+                expected
+            };
+            let interval: Interval = interval.parse().unwrap();
+            check(interval.checked_mul_decimal(factor), expected);
+        }
+
+        let interval: Interval = "1000000000:00:00.000001".parse().unwrap();
+        check(interval.checked_mul_f64(1.0), Ok("1000000000:00:00"));
+        check(
+            interval.checked_mul_decimal(Decimal::ONE),
+            Ok("1000000000:00:00.000001"),
+        );
+        let factor = Decimal::new(11, 1);
+        check(
+            Interval::new(1, 1, 1).checked_mul_decimal(factor),
+            Ok("1 mon 4 days 02:24:00.000001"),
+        );
+        check(
+            Interval::new(0, 0, 5).checked_mul_decimal(Decimal::new(5, 1)),
+            Ok("00:00:00.000002"),
+        );
+        check(
+            Interval::new(0, 0, 7).checked_mul_decimal(Decimal::new(5, 1)),
+            Ok("00:00:00.000004"),
+        );
+
+        // near `Decimal::MAX`, fail instead of overflowing the decimal
+        for interval in [
+            Interval::new(0, 0, 1),
+            Interval::new(0, 0, i64::MAX - 1),
+            Interval::new(0, 1, 0),
+            Interval::new(1, 0, 0),
+            Interval::new(0, 0, -1),
+        ] {
+            check(
+                interval.checked_mul_decimal(Decimal::MAX),
+                Err("interval out of range"),
+            );
+            check(
+                interval.checked_mul_decimal(Decimal::MIN),
+                Err("interval out of range"),
+            );
+        }
+        check(
+            Interval::new(0, 0, 0).checked_mul_decimal(Decimal::MAX),
+            Ok("00:00:00"),
+        );
+        check(
+            Interval::INFINITY.checked_mul_decimal(Decimal::MIN),
+            Ok("-infinity"),
+        );
+        check(
+            Interval::INFINITY.checked_mul_decimal(Decimal::ZERO),
+            Err("interval out of range"),
+        );
+    }
+}
@@ -0,0 +1,1717 @@
+//! Port of PostgreSQL's `interval_in` (`ParseDateTime`, `DecodeInterval` and
+//! `DecodeISO8601Interval` in `src/backend/utils/adt/datetime.c`).
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    DAYS_PER_MONTH, Interval, IntervalFields, IntervalStyle, MONTHS_PER_YEAR, OUT_OF_RANGE,
+    USECS_PER_DAY, USECS_PER_HOUR, USECS_PER_MINUTE, USECS_PER_SEC,
+};
+
+/// Unit and reserved words are compared on their first `TOKMAXLEN` characters.
+const TOKMAXLEN: usize = 10;
+/// Size of `interval_in`'s work buffer, which holds every field plus a
+/// terminator each, and its field limit (`MAXDATEFIELDS`).
+const WORKBUF_LEN: usize = 256;
+const MAX_DATE_FIELDS: usize = 25;
+
+// Field bits (`DTK_M(...)`) used to reject the same unit appearing twice.
+const MONTH: u32 = 1 << 1;
+const YEAR: u32 = 1 << 2;
+const DAY: u32 = 1 << 3;
+const HOUR: u32 = 1 << 10;
+const MINUTE: u32 = 1 << 11;
+const SECOND: u32 = 1 << 12;
+const MILLISECOND: u32 = 1 << 13;
+const MICROSECOND: u32 = 1 << 14;
+const WEEK: u32 = 1 << 24;
+const DECADE: u32 = 1 << 25;
+const CENTURY: u32 = 1 << 26;
+const MILLENNIUM: u32 = 1 << 27;
+const ALL_SECS: u32 = SECOND | MILLISECOND | MICROSECOND;
+const DATE_M: u32 = YEAR | MONTH | DAY;
+const TIME_M: u32 = HOUR | MINUTE | ALL_SECS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateTimeError {
+    /// `DTERR_BAD_FORMAT`
+    BadFormat,
+    /// `DTERR_FIELD_OVERFLOW`
+    FieldOverflow,
+}
+
+use DateTimeError::{BadFormat, FieldOverflow};
+
+type DtResult<T> = Result<T, DateTimeError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    Number,
+    String,
+    Date,
+    Time,
+    Tz,
+    Special,
+}
+
+#[derive(Debug)]
+struct Field {
+    text: String,
+    ftype: FieldType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// A unit word PostgreSQL knows but does not accept in an interval.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Units(Unit),
+    Ago,
+    Late,
+    Early,
+    /// Noise words such as "at".
+    Ignore,
+}
+
+/// `deltatktbl`, sorted.
+const DELTA_TOKENS: &[(&str, Token)] = &[
+    ("ago", Token::Ago),
+    ("c", Token::Units(Unit::Century)),
+    ("cent", Token::Units(Unit::Century)),
+    ("centuries", Token::Units(Unit::Century)),
+    ("century", Token::Units(Unit::Century)),
+    ("d", Token::Units(Unit::Day)),
+    ("day", Token::Units(Unit::Day)),
+    ("days", Token::Units(Unit::Day)),
+    ("dec", Token::Units(Unit::Decade)),
+    ("decade", Token::Units(Unit::Decade)),
+    ("decades", Token::Units(Unit::Decade)),
+    ("decs", Token::Units(Unit::Decade)),
+    ("h", Token::Units(Unit::Hour)),
+    ("hour", Token::Units(Unit::Hour)),
+    ("hours", Token::Units(Unit::Hour)),
+    ("hr", Token::Units(Unit::Hour)),
+    ("hrs", Token::Units(Unit::Hour)),
+    ("m", Token::Units(Unit::Minute)),
+    ("microsecon", Token::Units(Unit::Microsecond)),
+    ("mil", Token::Units(Unit::Millennium)),
+    ("millennia", Token::Units(Unit::Millennium)),
+    ("millennium", Token::Units(Unit::Millennium)),
+    ("millisecon", Token::Units(Unit::Millisecond)),
+    ("mils", Token::Units(Unit::Millennium)),
+    ("min", Token::Units(Unit::Minute)),
+    ("mins", Token::Units(Unit::Minute)),
+    ("minute", Token::Units(Unit::Minute)),
+    ("minutes", Token::Units(Unit::Minute)),
+    ("mon", Token::Units(Unit::Month)),
+    ("mons", Token::Units(Unit::Month)),
+    ("month", Token::Units(Unit::Month)),
+    ("months", Token::Units(Unit::Month)),
+    ("ms", Token::Units(Unit::Millisecond)),
+    ("msec", Token::Units(Unit::Millisecond)),
+    ("msecond", Token::Units(Unit::Millisecond)),
+    ("mseconds", Token::Units(Unit::Millisecond)),
+    ("msecs", Token::Units(Unit::Millisecond)),
+    ("qtr", Token::Units(Unit::Unsupported)),
+    ("quarter", Token::Units(Unit::Unsupported)),
+    ("s", Token::Units(Unit::Second)),
+    ("sec", Token::Units(Unit::Second)),
+    ("second", Token::Units(Unit::Second)),
+    ("seconds", Token::Units(Unit::Second)),
+    ("secs", Token::Units(Unit::Second)),
+    ("timezone", Token::Units(Unit::Unsupported)),
+    ("timezone_h", Token::Units(Unit::Unsupported)),
+    ("timezone_m", Token::Units(Unit::Unsupported)),
+    ("us", Token::Units(Unit::Microsecond)),
+    ("usec", Token::Units(Unit::Microsecond)),
+    ("usecond", Token::Units(Unit::Microsecond)),
+    ("useconds", Token::Units(Unit::Microsecond)),
+    ("usecs", Token::Units(Unit::Microsecond)),
+    ("w", Token::Units(Unit::Week)),
+    ("week", Token::Units(Unit::Week)),
+    ("weeks", Token::Units(Unit::Week)),
+    ("y", Token::Units(Unit::Year)),
+    ("year", Token::Units(Unit::Year)),
+    ("years", Token::Units(Unit::Year)),
+    ("yr", Token::Units(Unit::Year)),
+    ("yrs", Token::Units(Unit::Year)),
+];
+
+/// The entries of `datetktbl` that mean something inside an interval.
+const SPECIAL_TOKENS: &[(&str, Token)] = &[
+    ("+infinity", Token::Late),
+    ("-infinity", Token::Early),
+    ("at", Token::Ignore),
+    ("dow", Token::Units(Unit::Unsupported)),
+    ("doy", Token::Units(Unit::Unsupported)),
+    ("infinity", Token::Late),
+    ("isodow", Token::Units(Unit::Unsupported)),
+    ("isoyear", Token::Units(Unit::Unsupported)),
+    ("j", Token::Units(Unit::Unsupported)),
+    ("jd", Token::Units(Unit::Unsupported)),
+    ("julian", Token::Units(Unit::Unsupported)),
+    ("mm", Token::Units(Unit::Minute)),
+    ("on", Token::Ignore),
+];
+
+/// Every word in `datetktbl`; `ParseDateTime` uses it to decide whether a
+/// word directly followed by a digit starts a date.
+const DATE_TOKENS: &[&str] = &[
+    "-infinity",
+    "ad",
+    "allballs",
+    "am",
+    "apr",
+    "april",
+    "at",
+    "aug",
+    "august",
+    "bc",
+    "d",
+    "dec",
+    "december",
+    "dow",
+    "doy",
+    "dst",
+    "epoch",
+    "feb",
+    "february",
+    "fri",
+    "friday",
+    "h",
+    "infinity",
+    "isodow",
+    "isoyear",
+    "j",
+    "jan",
+    "january",
+    "jd",
+    "jul",
+    "julian",
+    "july",
+    "jun",
+    "june",
+    "m",
+    "mar",
+    "march",
+    "may",
+    "mm",
+    "mon",
+    "monday",
+    "nov",
+    "november",
+    "now",
+    "oct",
+    "october",
+    "on",
+    "pm",
+    "s",
+    "sat",
+    "saturday",
+    "sep",
+    "sept",
+    "september",
+    "sun",
+    "sunday",
+    "t",
+    "thu",
+    "thur",
+    "thurs",
+    "thursday",
+    "today",
+    "tomorrow",
+    "tue",
+    "tues",
+    "tuesday",
+    "wed",
+    "wednesday",
+    "weds",
+    "y",
+    "yesterday",
+];
+
+fn truncate_token(token: &str) -> &str {
+    token.get(..TOKMAXLEN).unwrap_or(token)
+}
+
+fn search<'a, T>(table: &'a [(&str, T)], token: &str) -> Option<&'a T> {
+    let key = truncate_token(token);
+    table
+        .binary_search_by(|(name, _)| name.cmp(&key))
+        .ok()
+        .map(|index| &table[index].1)
+}
+
+/// `DecodeUnits` followed by `DecodeSpecial`.
+fn lookup(token: &str) -> Option<Token> {
+    search(DELTA_TOKENS, token)
+        .or_else(|| search(SPECIAL_TOKENS, token))
+        .copied()
+}
+
+fn is_date_token(token: &str) -> bool {
+    DATE_TOKENS.binary_search(&truncate_token(token)).is_ok()
+}
+
+/// C `isspace` in the "C" locale.
+fn is_space(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | 0x0b | 0x0c | b'\r')
+}
+
+/// `struct pg_itm_in`: the fields accumulated while decoding.
+#[derive(Debug, Default)]
+struct ItmIn {
+    usec: i64,
+    mday: i32,
+    mon: i32,
+    year: i32,
+}
+
+impl ItmIn {
+    fn adjust_fract_microseconds(&mut self, frac: f64, scale: i64) -> DtResult<()> {
+        if frac == 0.0 {
+            return Ok(());
+        }
+        let frac = frac * scale as f64;
+        let usec = frac as i64;
+        // round off any fractional microsecond
+        let usec = usec + (frac - usec as f64).round_ties_even() as i64;
+        self.usec = self.usec.checked_add(usec).ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    fn adjust_fract_days(&mut self, frac: f64, scale: i32) -> DtResult<()> {
+        if frac == 0.0 {
+            return Ok(());
+        }
+        let frac = frac * f64::from(scale);
+        let extra_days = frac as i32;
+        self.mday = self.mday.checked_add(extra_days).ok_or(FieldOverflow)?;
+        self.adjust_fract_microseconds(frac - f64::from(extra_days), USECS_PER_DAY)
+    }
+
+    fn adjust_fract_years(&mut self, frac: f64, scale: i32) -> DtResult<()> {
+        let extra_months = (frac * f64::from(scale) * MONTHS_PER_YEAR as f64).round_ties_even();
+        self.mon = self
+            .mon
+            .checked_add(extra_months as i32)
+            .ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    fn adjust_microseconds(&mut self, val: i64, fval: f64, scale: i64) -> DtResult<()> {
+        let usec = val.checked_mul(scale).ok_or(FieldOverflow)?;
+        self.usec = self.usec.checked_add(usec).ok_or(FieldOverflow)?;
+        self.adjust_fract_microseconds(fval, scale)
+    }
+
+    fn adjust_days(&mut self, val: i64, scale: i32) -> DtResult<()> {
+        let days = i32::try_from(val)
+            .ok()
+            .and_then(|val| val.checked_mul(scale))
+            .ok_or(FieldOverflow)?;
+        self.mday = self.mday.checked_add(days).ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    fn adjust_months(&mut self, val: i64) -> DtResult<()> {
+        let months = i32::try_from(val).map_err(|_| FieldOverflow)?;
+        self.mon = self.mon.checked_add(months).ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    fn adjust_years(&mut self, val: i64, scale: i32) -> DtResult<()> {
+        let years = i32::try_from(val)
+            .ok()
+            .and_then(|val| val.checked_mul(scale))
+            .ok_or(FieldOverflow)?;
+        self.year = self.year.checked_add(years).ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    fn negate(&mut self) -> DtResult<()> {
+        self.usec = self.usec.checked_neg().ok_or(FieldOverflow)?;
+        self.mday = self.mday.checked_neg().ok_or(FieldOverflow)?;
+        self.mon = self.mon.checked_neg().ok_or(FieldOverflow)?;
+        self.year = self.year.checked_neg().ok_or(FieldOverflow)?;
+        Ok(())
+    }
+
+    /// `itmin2interval`
+    fn into_interval(self) -> Option<Interval> {
+        let months = i64::from(self.year) * MONTHS_PER_YEAR + i64::from(self.mon);
+        let interval = Interval {
+            months: months.try_into().ok()?,
+            days: self.mday,
+            microseconds: self.usec,
+        };
+        // the infinities' fields are reserved
+        interval.is_finite().then_some(interval)
+    }
+}
+
+#[derive(Debug)]
+enum Decoded {
+    Delta(ItmIn),
+    Late,
+    Early,
+}
+
+/// `strtol`: optional sign and decimal digits. Returns the value and the
+/// unparsed remainder, which is the whole input if there were no digits.
+fn strtol<T: TryFrom<i128>>(s: &str) -> DtResult<(T, &str)> {
+    let bytes = s.as_bytes();
+    let sign_len = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    let digits = bytes[sign_len..]
+        .iter()
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return T::try_from(0).map(|zero| (zero, s)).map_err(|_| BadFormat);
+    }
+    let end = sign_len + digits;
+    let value = s[..end]
+        .parse::<i128>()
+        .ok()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or(FieldOverflow)?;
+    Ok((value, &s[end..]))
+}
+
+/// `ParseFraction`: `s` starts with the decimal point.
+fn parse_fraction(s: &str) -> DtResult<f64> {
+    if s.len() == 1 {
+        return Ok(0.0);
+    }
+    if !s[1..].bytes().all(|c| c.is_ascii_digit()) {
+        return Err(BadFormat);
+    }
+    s.parse().map_err(|_| BadFormat)
+}
+
+/// `ParseFractionalSecond`: fraction of a second in microseconds.
+fn parse_fractional_second(s: &str) -> DtResult<i64> {
+    Ok((parse_fraction(s)? * USECS_PER_SEC as f64).round_ties_even() as i64)
+}
+
+/// `ParseDateTime`: break the string into fields.
+fn parse_date_time(input: &str) -> DtResult<Vec<Field>> {
+    let s = input.as_bytes();
+    let at = |pos: usize| s.get(pos).copied().unwrap_or(0);
+    let mut fields = Vec::new();
+    let mut used = 0;
+    let mut pos = 0;
+
+    while pos < s.len() {
+        let c = s[pos];
+        if is_space(c) {
+            pos += 1;
+            continue;
+        }
+        if fields.len() >= MAX_DATE_FIELDS {
+            return Err(BadFormat);
+        }
+
+        let start = pos;
+        let mut signed = None;
+        let mut lower = false;
+        let ftype;
+        if c.is_ascii_digit() {
+            pos += 1;
+            while at(pos).is_ascii_digit() {
+                pos += 1;
+            }
+            if at(pos) == b':' {
+                ftype = FieldType::Time;
+                pos += 1;
+                while matches!(at(pos), b'0'..=b'9' | b':' | b'.') {
+                    pos += 1;
+                }
+            } else if matches!(at(pos), b'-' | b'/' | b'.') {
+                let delim = at(pos);
+                pos += 1;
+                if at(pos).is_ascii_digit() {
+                    // a decimal point makes this a fractional number
+                    let mut date = delim != b'.';
+                    while at(pos).is_ascii_digit() {
+                        pos += 1;
+                    }
+                    if at(pos) == delim {
+                        date = true;
+                        pos += 1;
+                        while at(pos).is_ascii_digit() || at(pos) == delim {
+                            pos += 1;
+                        }
+                    }
+                    ftype = if date {
+                        FieldType::Date
+                    } else {
+                        FieldType::Number
+                    };
+                } else {
+                    ftype = FieldType::Date;
+                    lower = true;
+                    while at(pos).is_ascii_alphanumeric() || at(pos) == delim {
+                        pos += 1;
+                    }
+                }
+            } else {
+                ftype = FieldType::Number;
+            }
+        } else if c == b'.' {
+            pos += 1;
+            while at(pos).is_ascii_digit() {
+                pos += 1;
+            }
+            ftype = FieldType::Number;
+        } else if c.is_ascii_alphabetic() {
+            lower = true;
+            pos += 1;
+            while at(pos).is_ascii_alphabetic() {
+                pos += 1;
+            }
+            // words may run into date separators or digits, e.g. "p1y2m"
+            let is_date = match at(pos) {
+                b'-' | b'/' | b'.' => true,
+                b'+' | b'0'..=b'9' => !is_date_token(&input[start..pos].to_ascii_lowercase()),
+                _ => false,
+            };
+            if is_date {
+                pos += 1;
+                while matches!(at(pos), b'+' | b'-' | b'/' | b'_' | b'.' | b':')
+                    || at(pos).is_ascii_alphanumeric()
+                {
+                    pos += 1;
+                }
+                ftype = FieldType::Date;
+            } else {
+                ftype = FieldType::String;
+            }
+        } else if c == b'+' || c == b'-' {
+            pos += 1;
+            // whitespace between the sign and the value is dropped
+            while is_space(at(pos)) {
+                pos += 1;
+            }
+            let sign = &input[start..start + 1];
+            let value_start = pos;
+            if at(pos).is_ascii_digit() {
+                pos += 1;
+                while matches!(at(pos), b'0'..=b'9' | b':' | b'.' | b'-') {
+                    pos += 1;
+                }
+                ftype = FieldType::Tz;
+            } else if at(pos).is_ascii_alphabetic() {
+                pos += 1;
+                while at(pos).is_ascii_alphabetic() {
+                    pos += 1;
+                }
+                ftype = FieldType::Special;
+            } else {
+                return Err(BadFormat);
+            }
+            signed = Some(format!(
+                "{sign}{}",
+                input[value_start..pos].to_ascii_lowercase()
+            ));
+        } else if c.is_ascii_punctuation() {
+            // other punctuation only delimits fields
+            pos += 1;
+            continue;
+        } else {
+            return Err(BadFormat);
+        }
+
+        let text = signed.unwrap_or_else(|| {
+            let text = &input[start..pos];
+            if lower {
+                text.to_ascii_lowercase()
+            } else {
+                text.to_owned()
+            }
+        });
+        // every field is stored with a terminator
+        used += text.len() + 1;
+        if used > WORKBUF_LEN {
+            return Err(BadFormat);
+        }
+        fields.push(Field { text, ftype });
+    }
+
+    Ok(fields)
+}
+
+/// `DecodeTimeForInterval`: `hh:mm[:ss[.fff]]` or `mm:ss.fff` in microseconds,
+/// or `mm:ss` for `MINUTE TO SECOND`.
+fn decode_time_for_interval(s: &str, range: IntervalFields) -> DtResult<i64> {
+    let (mut hour, rest) = strtol::<i64>(s)?;
+    let rest = rest.strip_prefix(':').ok_or(BadFormat)?;
+    let (mut min, rest) = strtol::<i32>(rest)?;
+    let mut sec = 0;
+    let mut fsec = 0;
+    if rest.is_empty() && range == IntervalFields::MinuteToSecond {
+        // take the two fields as mm:ss
+        sec = min;
+        min = i32::try_from(hour).map_err(|_| FieldOverflow)?;
+        hour = 0;
+    } else if rest.starts_with('.') {
+        // always assume mm:ss.sss is MINUTE TO SECOND
+        fsec = parse_fractional_second(rest)?;
+        sec = min;
+        min = i32::try_from(hour).map_err(|_| FieldOverflow)?;
+        hour = 0;
+    } else if let Some(rest) = rest.strip_prefix(':') {
+        let (s, rest) = strtol::<i32>(rest)?;
+        sec = s;
+        if rest.starts_with('.') {
+            fsec = parse_fractional_second(rest)?;
+        } else if !rest.is_empty() {
+            return Err(BadFormat);
+        }
+    } else if !rest.is_empty() {
+        return Err(BadFormat);
+    }
+
+    if hour < 0
+        || !(0..=59).contains(&min)
+        || !(0..=60).contains(&sec)
+        || !(0..=USECS_PER_SEC).contains(&fsec)
+    {
+        return Err(FieldOverflow);
+    }
+
+    hour.checked_mul(USECS_PER_HOUR)
+        .and_then(|usec| usec.checked_add(i64::from(min) * USECS_PER_MINUTE))
+        .and_then(|usec| usec.checked_add(i64::from(sec) * USECS_PER_SEC))
+        .and_then(|usec| usec.checked_add(fsec))
+        .ok_or(FieldOverflow)
+}
+
+/// `DecodeInterval`: interpret the fields of a traditional interval string.
+fn decode_interval(
+    fields: &[Field],
+    style: IntervalStyle,
+    range: IntervalFields,
+) -> DtResult<Decoded> {
+    let mut itm = ItmIn::default();
+    let mut special = None;
+    let mut is_before = false;
+    let mut parsing_unit_val = false;
+    let mut fmask = 0;
+    // `None` is PostgreSQL's `IGNORE_DTF`: no unit seen yet
+    let mut unit: Option<Unit> = None;
+
+    // The SQL standard reads '-1 1:00:00' as "negative 1 day and negative
+    // 1 hour", while Postgres traditionally reads "negative 1 day and
+    // positive 1 hour". In sql_standard style the leading sign applies to all
+    // fields if there are no other explicit signs.
+    let force_negative = style == IntervalStyle::SqlStandard
+        && fields
+            .first()
+            .is_some_and(|field| field.text.starts_with('-'))
+        && !fields[1..]
+            .iter()
+            .any(|field| field.text.starts_with(['-', '+']));
+
+    // read through the list backwards to pick up units before values
+    for (i, field) in fields.iter().enumerate().rev() {
+        let text = field.text.as_str();
+
+        // signed hh:mm[:ss] is handled exactly like an unsigned time
+        let mut time = None;
+        if field.ftype == FieldType::Time {
+            time = Some(decode_time_for_interval(text, range)?);
+        } else if field.ftype == FieldType::Tz
+            && text[1..].contains(':')
+            && let Ok(usec) = decode_time_for_interval(&text[1..], range)
+        {
+            time = Some(if text.starts_with('-') { -usec } else { usec });
+        }
+
+        let tmask = if let Some(usec) = time {
+            itm.usec = usec;
+            if force_negative && itm.usec > 0 {
+                itm.usec = -itm.usec;
+            }
+            // a number before a time, e.g. '1 +02:03', is a number of days
+            unit = Some(Unit::Day);
+            parsing_unit_val = false;
+            TIME_M
+        } else {
+            match field.ftype {
+                FieldType::Time | FieldType::Tz | FieldType::Date | FieldType::Number => {
+                    // the column's last field is the unit of a bare number
+                    let mut current = *unit.get_or_insert(match range {
+                        IntervalFields::Year => Unit::Year,
+                        IntervalFields::Month | IntervalFields::YearToMonth => Unit::Month,
+                        IntervalFields::Day => Unit::Day,
+                        IntervalFields::Hour | IntervalFields::DayToHour => Unit::Hour,
+                        IntervalFields::Minute
+                        | IntervalFields::HourToMinute
+                        | IntervalFields::DayToMinute => Unit::Minute,
+                        _ => Unit::Second,
+                    });
+                    let (mut val, rest) = strtol::<i64>(text)?;
+                    let mut fval;
+                    if let Some(rest) = rest.strip_prefix('-') {
+                        // SQL "years-months" syntax
+                        let (mut val2, rest) = strtol::<i32>(rest)?;
+                        if !(0..MONTHS_PER_YEAR as i32).contains(&val2) {
+                            return Err(FieldOverflow);
+                        }
+                        if !rest.is_empty() {
+                            return Err(BadFormat);
+                        }
+                        current = Unit::Month;
+                        unit = Some(current);
+                        if text.starts_with('-') {
+                            val2 = -val2;
+                        }
+                        val = val
+                            .checked_mul(MONTHS_PER_YEAR)
+                            .and_then(|val| val.checked_add(i64::from(val2)))
+                            .ok_or(FieldOverflow)?;
+                        fval = 0.0;
+                    } else if rest.starts_with('.') {
+                        fval = parse_fraction(rest)?;
+                        if text.starts_with('-') {
+                            fval = -fval;
+                        }
+                    } else if rest.is_empty() {
+                        fval = 0.0;
+                    } else {
+                        return Err(BadFormat);
+                    }
+
+                    if force_negative {
+                        if val > 0 {
+                            val = -val;
+                        }
+                        if fval > 0.0 {
+                            fval = -fval;
+                        }
+                    }
+
+                    parsing_unit_val = false;
+                    match current {
+                        Unit::Microsecond => {
+                            itm.adjust_microseconds(val, fval, 1)?;
+                            MICROSECOND
+                        }
+                        Unit::Millisecond => {
+                            itm.adjust_microseconds(val, fval, 1000)?;
+                            MILLISECOND
+                        }
+                        Unit::Second => {
+                            itm.adjust_microseconds(val, fval, USECS_PER_SEC)?;
+                            // any subseconds count as millisecond and
+                            // microsecond input as well
+                            if fval == 0.0 { SECOND } else { ALL_SECS }
+                        }
+                        Unit::Minute => {
+                            itm.adjust_microseconds(val, fval, USECS_PER_MINUTE)?;
+                            MINUTE
+                        }
+                        Unit::Hour => {
+                            itm.adjust_microseconds(val, fval, USECS_PER_HOUR)?;
+                            unit = Some(Unit::Day);
+                            HOUR
+                        }
+                        Unit::Day => {
+                            itm.adjust_days(val, 1)?;
+                            itm.adjust_fract_microseconds(fval, USECS_PER_DAY)?;
+                            DAY
+                        }
+                        Unit::Week => {
+                            itm.adjust_days(val, 7)?;
+                            itm.adjust_fract_days(fval, 7)?;
+                            WEEK
+                        }
+                        Unit::Month => {
+                            itm.adjust_months(val)?;
+                            itm.adjust_fract_days(fval, DAYS_PER_MONTH)?;
+                            MONTH
+                        }
+                        Unit::Year => {
+                            itm.adjust_years(val, 1)?;
+                            itm.adjust_fract_years(fval, 1)?;
+                            YEAR
+                        }
+                        Unit::Decade => {
+                            itm.adjust_years(val, 10)?;
+                            itm.adjust_fract_years(fval, 10)?;
+                            DECADE
+                        }
+                        Unit::Century => {
+                            itm.adjust_years(val, 100)?;
+                            itm.adjust_fract_years(fval, 100)?;
+                            CENTURY
+                        }
+                        Unit::Millennium => {
+                            itm.adjust_years(val, 1000)?;
+                            itm.adjust_fract_years(fval, 1000)?;
+                            MILLENNIUM
+                        }
+                        Unit::Unsupported => return Err(BadFormat),
+                    }
+                }
+                FieldType::String | FieldType::Special => {
+                    // reject consecutive unhandled units
+                    if parsing_unit_val {
+                        return Err(BadFormat);
+                    }
+                    match lookup(text).ok_or(BadFormat)? {
+                        Token::Ignore => continue,
+                        Token::Units(u) => {
+                            unit = Some(u);
+                            parsing_unit_val = true;
+                            0
+                        }
+                        Token::Ago => {
+                            // "ago" is only allowed at the end
+                            if i != fields.len() - 1 {
+                                return Err(BadFormat);
+                            }
+                            is_before = true;
+                            unit = Some(Unit::Unsupported);
+                            0
+                        }
+                        token @ (Token::Late | Token::Early) => {
+                            // infinity cannot be followed by anything else
+                            if i != fields.len() - 1 {
+                                return Err(BadFormat);
+                            }
+                            special = Some(token);
+                            DATE_M | TIME_M
+                        }
+                    }
+                }
+            }
+        };
+
+        if tmask & fmask != 0 {
+            return Err(BadFormat);
+        }
+        fmask |= tmask;
+    }
+
+    // at least one field must have been found, and every unit used
+    if fmask == 0 || parsing_unit_val {
+        return Err(BadFormat);
+    }
+
+    // finally, "ago" negates everything
+    if is_before {
+        itm.negate()?;
+    }
+
+    Ok(match special {
+        Some(Token::Late) => Decoded::Late,
+        Some(Token::Early) => Decoded::Early,
+        _ => Decoded::Delta(itm),
+    })
+}
+
+/// `strtod`: returns the value and the number of bytes consumed, or `None`
+/// if there is no number or it is out of range.
+fn strtod(s: &[u8]) -> Option<(f64, usize)> {
+    let at = |pos: usize| s.get(pos).copied().unwrap_or(0);
+    let mut end = usize::from(matches!(at(0), b'+' | b'-'));
+
+    for word in ["infinity", "inf", "nan"] {
+        if s.len() >= end + word.len()
+            && s[end..end + word.len()].eq_ignore_ascii_case(word.as_bytes())
+        {
+            let value = if word == "nan" {
+                f64::NAN
+            } else {
+                f64::INFINITY
+            };
+            let value = if at(0) == b'-' { -value } else { value };
+            return Some((value, end + word.len()));
+        }
+    }
+
+    let mantissa = end;
+    while at(end).is_ascii_digit() {
+        end += 1;
+    }
+    if at(end) == b'.' {
+        end += 1;
+        while at(end).is_ascii_digit() {
+            end += 1;
+        }
+    }
+    let digits = &s[mantissa..end];
+    if !digits.iter().any(u8::is_ascii_digit) {
+        return None;
+    }
+    let nonzero = digits.iter().any(|c| matches!(c, b'1'..=b'9'));
+    if matches!(at(end), b'e' | b'E') {
+        let mut exp_end = end + 1;
+        if matches!(at(exp_end), b'+' | b'-') {
+            exp_end += 1;
+        }
+        if at(exp_end).is_ascii_digit() {
+            while at(exp_end).is_ascii_digit() {
+                exp_end += 1;
+            }
+            end = exp_end;
+        }
+    }
+
+    let value: f64 = std::str::from_utf8(&s[..end]).ok()?.parse().ok()?;
+    // ERANGE: overflow or underflow
+    if value.is_infinite() || (nonzero && !value.is_normal()) {
+        return None;
+    }
+    Some((value, end))
+}
+
+/// `ParseISO8601Number`: a number split into its integral part and the
+/// remaining fraction (which has the same sign), plus the bytes consumed.
+fn parse_iso8601_number(s: &[u8]) -> DtResult<(i64, f64, usize)> {
+    if !matches!(s.first(), Some(b'0'..=b'9' | b'-' | b'.')) {
+        return Err(BadFormat);
+    }
+    let (value, len) = strtod(s).ok_or(BadFormat)?;
+    // integral parts above 1e15 would not be exact
+    if value.is_nan() || !(-1.0e15..=1.0e15).contains(&value) {
+        return Err(FieldOverflow);
+    }
+    let ipart = value.trunc() as i64;
+    Ok((ipart, value - ipart as f64, len))
+}
+
+/// `ISO8601IntegerWidth`: the number of digits in the integral part.
+fn iso8601_integer_width(s: &[u8]) -> usize {
+    let s = s.strip_prefix(b"-").unwrap_or(s);
+    s.iter().take_while(|c| c.is_ascii_digit()).count()
+}
+
+/// `DecodeISO8601Interval`: the format with designators,
+/// `P[n]Y[n]M[n]W[n]DT[n]H[n]M[n]S`, or the alternative format,
+/// `PYYYYMMDDThhmmss` or `PYYYY-MM-DDThh:mm:ss`.
+fn decode_iso8601_interval(input: &str) -> DtResult<Decoded> {
+    let s = input.as_bytes();
+    if s.len() < 2 || s[0] != b'P' {
+        return Err(BadFormat);
+    }
+
+    let at = |pos: usize| s.get(pos).copied().unwrap_or(0);
+    let mut itm = ItmIn::default();
+    let mut datepart = true;
+    let mut havefield = false;
+    let mut pos = 1;
+    while pos < s.len() {
+        if s[pos] == b'T' {
+            // T starts the time part
+            datepart = false;
+            havefield = false;
+            pos += 1;
+            continue;
+        }
+
+        let fieldstart = pos;
+        let (val, fval, len) = parse_iso8601_number(&s[pos..])?;
+        pos += len;
+        // a zero unit is the end of the string
+        let unit = at(pos);
+        pos += 1;
+
+        if datepart {
+            match unit {
+                b'Y' => {
+                    itm.adjust_years(val, 1)?;
+                    itm.adjust_fract_years(fval, 1)?;
+                }
+                b'M' => {
+                    itm.adjust_months(val)?;
+                    itm.adjust_fract_days(fval, DAYS_PER_MONTH)?;
+                }
+                b'W' => {
+                    itm.adjust_days(val, 7)?;
+                    itm.adjust_fract_days(fval, 7)?;
+                }
+                b'D' => {
+                    itm.adjust_days(val, 1)?;
+                    itm.adjust_fract_microseconds(fval, USECS_PER_DAY)?;
+                }
+                b'T' | 0 | b'-' => {
+                    // alternative format, basic: YYYYMMDD
+                    if unit != b'-' && iso8601_integer_width(&s[fieldstart..]) == 8 && !havefield {
+                        itm.adjust_years(val / 10000, 1)?;
+                        itm.adjust_months((val / 100) % 100)?;
+                        itm.adjust_days(val % 100, 1)?;
+                        itm.adjust_fract_microseconds(fval, USECS_PER_DAY)?;
+                        if unit == 0 {
+                            break;
+                        }
+                        datepart = false;
+                        havefield = false;
+                        continue;
+                    }
+
+                    // alternative format, extended: YYYY-MM-DD
+                    if havefield {
+                        return Err(BadFormat);
+                    }
+                    itm.adjust_years(val, 1)?;
+                    itm.adjust_fract_years(fval, 1)?;
+                    if unit == 0 {
+                        break;
+                    }
+                    if unit == b'T' {
+                        datepart = false;
+                        havefield = false;
+                        continue;
+                    }
+
+                    let (val, fval, len) = parse_iso8601_number(&s[pos..])?;
+                    pos += len;
+                    itm.adjust_months(val)?;
+                    itm.adjust_fract_days(fval, DAYS_PER_MONTH)?;
+                    match at(pos) {
+                        0 => break,
+                        b'T' => {
+                            datepart = false;
+                            havefield = false;
+                            continue;
+                        }
+                        b'-' => pos += 1,
+                        _ => return Err(BadFormat),
+                    }
+
+                    let (val, fval, len) = parse_iso8601_number(&s[pos..])?;
+                    pos += len;
+                    itm.adjust_days(val, 1)?;
+                    itm.adjust_fract_microseconds(fval, USECS_PER_DAY)?;
+                    match at(pos) {
+                        0 => break,
+                        b'T' => {
+                            datepart = false;
+                            havefield = false;
+                            continue;
+                        }
+                        _ => return Err(BadFormat),
+                    }
+                }
+                _ => return Err(BadFormat),
+            }
+        } else {
+            match unit {
+                b'H' => itm.adjust_microseconds(val, fval, USECS_PER_HOUR)?,
+                b'M' => itm.adjust_microseconds(val, fval, USECS_PER_MINUTE)?,
+                b'S' => itm.adjust_microseconds(val, fval, USECS_PER_SEC)?,
+                0 | b':' => {
+                    // alternative format, basic: hhmmss
+                    if unit == 0 && iso8601_integer_width(&s[fieldstart..]) == 6 && !havefield {
+                        itm.adjust_microseconds(val / 10000, 0.0, USECS_PER_HOUR)?;
+                        itm.adjust_microseconds((val / 100) % 100, 0.0, USECS_PER_MINUTE)?;
+                        itm.adjust_microseconds(val % 100, 0.0, USECS_PER_SEC)?;
+                        itm.adjust_fract_microseconds(fval, 1)?;
+                        break;
+                    }
+
+                    // alternative format, extended: hh:mm:ss
+                    if havefield {
+                        return Err(BadFormat);
+                    }
+                    itm.adjust_microseconds(val, fval, USECS_PER_HOUR)?;
+                    if unit == 0 {
+                        break;
+                    }
+
+                    let (val, fval, len) = parse_iso8601_number(&s[pos..])?;
+                    pos += len;
+                    itm.adjust_microseconds(val, fval, USECS_PER_MINUTE)?;
+                    match at(pos) {
+                        0 => break,
+                        b':' => pos += 1,
+                        _ => return Err(BadFormat),
+                    }
+
+                    let (val, fval, len) = parse_iso8601_number(&s[pos..])?;
+                    pos += len;
+                    itm.adjust_microseconds(val, fval, USECS_PER_SEC)?;
+                    if at(pos) == 0 {
+                        break;
+                    }
+                    return Err(BadFormat);
+                }
+                _ => return Err(BadFormat),
+            }
+        }
+
+        havefield = true;
+    }
+
+    Ok(Decoded::Delta(itm))
+}
+
+/// Parse `input` exactly like PostgreSQL's `interval_in` does when the session
+/// uses the given `IntervalStyle` and the target type has the given fields.
+pub(crate) fn parse_interval(
+    input: &str,
+    style: IntervalStyle,
+    range: IntervalFields,
+) -> Result<Interval, BoxDynError> {
+    let decoded = parse_date_time(input)
+        .and_then(|fields| decode_interval(&fields, style, range))
+        .or_else(|error| match error {
+            // if the traditional parser thinks it's a bad format, try ISO 8601
+            BadFormat => decode_iso8601_interval(input),
+            error => Err(error),
+        });
+
+    finish(input, decoded)
+}
+
+/// Parse ISO 8601 input only, in either of the formats `interval_in` reads,
+/// with an optional leading sign that applies to every field (ISO 8601-2):
+/// `-P1DT2H` is `P-1DT-2H`, and `-P1Y-2M` is `P-1Y2M`. The infinities are
+/// read as `interval_in` reads them, since `iso_8601` output writes them so.
+pub(crate) fn parse_iso8601(input: &str) -> Result<Interval, BoxDynError> {
+    let (negative, unsigned) = match input.as_bytes().first() {
+        Some(b'-') => (true, &input[1..]),
+        Some(b'+') => (false, &input[1..]),
+        _ => (false, input),
+    };
+    let decoded = if unsigned.eq_ignore_ascii_case("infinity") {
+        Ok(if negative {
+            Decoded::Early
+        } else {
+            Decoded::Late
+        })
+    } else {
+        decode_iso8601_interval(unsigned).and_then(|decoded| match decoded {
+            Decoded::Delta(mut itm) if negative => {
+                itm.negate()?;
+                Ok(Decoded::Delta(itm))
+            }
+            decoded => Ok(decoded),
+        })
+    };
+    finish(input, decoded)
+}
+
+/// Parse a string for `Deserialize`: anything `interval_in` accepts for a
+/// type with the given fields, with the `sql_standard` sign rules `Decode`
+/// uses for server output, and ISO 8601 with a leading sign as in
+/// [`parse_iso8601`], which `interval_in` rejects.
+#[cfg(not(feature = "serde-struct"))]
+pub(crate) fn parse_deserialized(
+    input: &str,
+    range: IntervalFields,
+) -> Result<Interval, BoxDynError> {
+    if input.starts_with(['-', '+']) && input[1..].starts_with('P') {
+        return parse_iso8601(input);
+    }
+    parse_interval(input, IntervalStyle::SqlStandard, range)
+}
+
+/// Convert the decoded fields, or the error, into `interval_in`'s result.
+fn finish(input: &str, decoded: DtResult<Decoded>) -> Result<Interval, BoxDynError> {
+    match decoded {
+        Ok(Decoded::Delta(itm)) => Ok(itm.into_interval().ok_or(OUT_OF_RANGE)?),
+        Ok(Decoded::Late) => Ok(Interval::INFINITY),
+        Ok(Decoded::Early) => Ok(Interval::NEG_INFINITY),
+        Err(BadFormat) => {
+            Err(format!("invalid input syntax for type interval: \"{input}\"").into())
+        }
+        Err(FieldOverflow) => Err(format!("interval field value out of range: \"{input}\"").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, IntervalFields::*, IntervalStyle, IntervalStyle::*};
+
+    // Cases from PostgreSQL's `src/test/regress/sql/interval.sql`, with the
+    // results in the PostgreSQL 17 `interval.out` (which sets the same
+    // `IntervalStyle` for input and output).
+
+    const SYNTAX: &str = "invalid input syntax for type interval";
+    const FIELD: &str = "interval field value out of range";
+    const RANGE: &str = "interval out of range";
+
+    fn message(kind: &str, input: &str) -> String {
+        if kind == RANGE {
+            kind.to_owned()
+        } else {
+            format!("{kind}: \"{input}\"")
+        }
+    }
+
+    fn check(style: IntervalStyle, cases: &[(&str, Result<&str, &str>)]) {
+        for &(input, expected) in cases {
+            let actual = super::parse_interval(input, style, All)
+                .map(|interval| interval.display(style).to_string())
+                .map_err(|e| e.to_string());
+            assert_eq!(
+                actual,
+                expected
+                    .map(str::to_owned)
+                    .map_err(|kind| message(kind, input))
+            );
+        }
+    }
+
+    #[test]
+    fn regress_input() {
+        // Values, syntax errors and rounding from the first part of the file.
+        check(
+            PostgresVerbose,
+            &[
+                ("01:00", Ok("@ 1 hour")),
+                ("+02:00", Ok("@ 2 hours")),
+                ("-08:00", Ok("@ 8 hours ago")),
+                ("-1 +02:03", Ok("@ 1 day -2 hours -3 mins ago")),
+                ("-1 days +02:03", Ok("@ 1 day -2 hours -3 mins ago")),
+                ("1.5 weeks", Ok("@ 10 days 12 hours")),
+                ("1.5 months", Ok("@ 1 mon 15 days")),
+                (
+                    "10 years -11 month -12 days +13:14",
+                    Ok("@ 9 years 1 mon -12 days 13 hours 14 mins"),
+                ),
+                ("infinity", Ok("infinity")),
+                ("-infinity", Ok("-infinity")),
+                ("@ 1 minute", Ok("@ 1 min")),
+                ("@ 5 hour", Ok("@ 5 hours")),
+                ("@ 10 day", Ok("@ 10 days")),
+                ("@ 34 year", Ok("@ 34 years")),
+                ("@ 3 months", Ok("@ 3 mons")),
+                ("@ 14 seconds ago", Ok("@ 14 secs ago")),
+                (
+                    "1 day 2 hours 3 minutes 4 seconds",
+                    Ok("@ 1 day 2 hours 3 mins 4 secs"),
+                ),
+                ("6 years", Ok("@ 6 years")),
+                ("5 months", Ok("@ 5 mons")),
+                ("5 months 12 hours", Ok("@ 5 mons 12 hours")),
+                ("badly formatted interval", Err(SYNTAX)),
+                ("@ 30 eons ago", Err(SYNTAX)),
+                ("garbage", Err(SYNTAX)),
+                (
+                    "4 millenniums 5 centuries 4 decades 1 year 4 months 4 days 17 minutes 31 seconds",
+                    Ok("@ 4541 years 4 mons 4 days 17 mins 31 secs"),
+                ),
+                (
+                    "100000000y 10mon -1000000000d -100000h -10min -10.000001s ago",
+                    Ok(
+                        "@ 100000000 years 10 mons -1000000000 days -100000 hours -10 mins -10.000001 secs ago",
+                    ),
+                ),
+                (
+                    "-10 mons -3 days +03:55:06.70",
+                    Ok("@ 10 mons 3 days -3 hours -55 mins -6.7 secs ago"),
+                ),
+                (
+                    "1 year 2 mons 3 days 04:05:06.699999",
+                    Ok("@ 1 year 2 mons 3 days 4 hours 5 mins 6.699999 secs"),
+                ),
+                ("0:0:0.7", Ok("@ 0.7 secs")),
+                ("@ 0.70 secs", Ok("@ 0.7 secs")),
+                ("0.7 seconds", Ok("@ 0.7 secs")),
+            ],
+        );
+    }
+
+    #[test]
+    fn regress_sign_rules() {
+        // Sign handling, including the sql_standard rule that a leading sign applies to every field.
+        check(
+            SqlStandard,
+            &[
+                ("0", Ok("0")),
+                ("1-2", Ok("1-2")),
+                ("1 2:03:04", Ok("1 2:03:04")),
+            ],
+        );
+        check(
+            Postgres,
+            &[
+                ("+1 -1:00:00", Ok("1 day -01:00:00")),
+                ("-1 +1:00:00", Ok("-1 days +01:00:00")),
+                (
+                    "+1-2 -3 +4:05:06.789",
+                    Ok("1 year 2 mons -3 days +04:05:06.789"),
+                ),
+                (
+                    "-1-2 +3 -4:05:06.789",
+                    Ok("-1 years -2 mons +3 days -04:05:06.789"),
+                ),
+                ("-23 hours 45 min 12.34 sec", Ok("-22:14:47.66")),
+                (
+                    "-1 day 23 hours 45 min 12.34 sec",
+                    Ok("-1 days +23:45:12.34"),
+                ),
+                (
+                    "-1 year 2 months 1 day 23 hours 45 min 12.34 sec",
+                    Ok("-10 mons +1 day 23:45:12.34"),
+                ),
+                (
+                    "-1 year 2 months 1 day 23 hours 45 min +12.34 sec",
+                    Ok("-10 mons +1 day 23:45:12.34"),
+                ),
+            ],
+        );
+        check(
+            SqlStandard,
+            &[
+                ("1 day -1 hours", Ok("+0-0 +1 -1:00:00")),
+                ("-1 days +1 hours", Ok("+0-0 -1 +1:00:00")),
+                (
+                    "1 years 2 months -3 days 4 hours 5 minutes 6.789 seconds",
+                    Ok("+1-2 -3 +4:05:06.789"),
+                ),
+                ("-23 hours 45 min 12.34 sec", Ok("-23:45:12.34")),
+                ("-1 day 23 hours 45 min 12.34 sec", Ok("-1 23:45:12.34")),
+                (
+                    "-1 year 2 months 1 day 23 hours 45 min 12.34 sec",
+                    Ok("-1-2 -1 -23:45:12.34"),
+                ),
+                (
+                    "-1 year 2 months 1 day 23 hours 45 min +12.34 sec",
+                    Ok("-0-10 +1 +23:45:12.34"),
+                ),
+                ("", Err(SYNTAX)),
+            ],
+        );
+    }
+
+    #[test]
+    fn regress_iso8601() {
+        // ISO 8601 input in both formats, with fractional fields.
+        check(
+            Iso8601,
+            &[
+                ("0", Ok("PT0S")),
+                ("1-2", Ok("P1Y2M")),
+                ("1 2:03:04", Ok("P1DT2H3M4S")),
+                ("2:03:04.45679", Ok("PT2H3M4.45679S")),
+            ],
+        );
+        check(
+            SqlStandard,
+            &[
+                ("P0Y", Ok("0")),
+                ("P1Y2M", Ok("1-2")),
+                ("P1W", Ok("7 0:00:00")),
+                ("P1DT2H3M4S", Ok("1 2:03:04")),
+                ("P1Y2M3DT4H5M6.7S", Ok("+1-2 +3 +4:05:06.7")),
+                ("P-1Y-2M-3DT-4H-5M-6.7S", Ok("-1-2 -3 -4:05:06.7")),
+                ("PT-0.1S", Ok("-0:00:00.1")),
+            ],
+        );
+        check(
+            Postgres,
+            &[
+                ("P00021015T103020", Ok("2 years 10 mons 15 days 10:30:20")),
+                (
+                    "P0002-10-15T10:30:20",
+                    Ok("2 years 10 mons 15 days 10:30:20"),
+                ),
+                ("P0002", Ok("2 years")),
+                ("P0002-10", Ok("2 years 10 mons")),
+                ("P0002-10-15", Ok("2 years 10 mons 15 days")),
+                ("P0002T1S", Ok("2 years 00:00:01")),
+                ("P0002-10T1S", Ok("2 years 10 mons 00:00:01")),
+                ("P0002-10-15T1S", Ok("2 years 10 mons 15 days 00:00:01")),
+                ("PT10", Ok("10:00:00")),
+                ("PT10:30", Ok("10:30:00")),
+                ("P1Y0M3DT4H5M6S", Ok("1 year 3 days 04:05:06")),
+                ("P1.0Y0M3DT4H5M6S", Ok("1 year 3 days 04:05:06")),
+                ("P1.1Y0M3DT4H5M6S", Ok("1 year 1 mon 3 days 04:05:06")),
+                ("P1.Y0M3DT4H5M6S", Ok("1 year 3 days 04:05:06")),
+                ("P.1Y0M3DT4H5M6S", Ok("1 mon 3 days 04:05:06")),
+                ("P10.5e4Y", Ok("105000 years")),
+                ("P.Y0M3DT4H5M6S", Err(SYNTAX)),
+            ],
+        );
+    }
+
+    #[test]
+    fn regress_full_range() {
+        // Time fields using the entire 64-bit microseconds range.
+        check(
+            Postgres,
+            &[
+                (
+                    "2562047788.01521550194 hours",
+                    Ok("2562047788:00:54.775807"),
+                ),
+                (
+                    "-2562047788.01521550222 hours",
+                    Ok("-2562047788:00:54.775808"),
+                ),
+                (
+                    "153722867280.912930117 minutes",
+                    Ok("2562047788:00:54.775807"),
+                ),
+                (
+                    "-153722867280.912930133 minutes",
+                    Ok("-2562047788:00:54.775808"),
+                ),
+                (
+                    "9223372036854.775807 seconds",
+                    Ok("2562047788:00:54.775807"),
+                ),
+                (
+                    "-9223372036854.775808 seconds",
+                    Ok("-2562047788:00:54.775808"),
+                ),
+                (
+                    "9223372036854775.807 milliseconds",
+                    Ok("2562047788:00:54.775807"),
+                ),
+                (
+                    "-9223372036854775.808 milliseconds",
+                    Ok("-2562047788:00:54.775808"),
+                ),
+                (
+                    "9223372036854775807 microseconds",
+                    Ok("2562047788:00:54.775807"),
+                ),
+                (
+                    "-9223372036854775808 microseconds",
+                    Ok("-2562047788:00:54.775808"),
+                ),
+                ("PT2562047788H54.775807S", Ok("2562047788:00:54.775807")),
+                ("PT-2562047788H-54.775808S", Ok("-2562047788:00:54.775808")),
+                ("PT2562047788:00:54.775807", Ok("2562047788:00:54.775807")),
+                ("PT2562047788.0152155019444", Ok("2562047788:00:54.775429")),
+                (
+                    "PT-2562047788.0152155022222",
+                    Ok("-2562047788:00:54.775429"),
+                ),
+                (
+                    "-2147483648 months -2147483647 days -9223372036854775807 us",
+                    Ok("-178956970 years -8 mons -2147483647 days -2562047788:00:54.775807"),
+                ),
+            ],
+        );
+        check(
+            SqlStandard,
+            &[(
+                "-2147483648 months -2147483647 days -9223372036854775807 us",
+                Ok("-178956970-8 -2147483647 -2562047788:00:54.775807"),
+            )],
+        );
+        check(
+            Iso8601,
+            &[(
+                "-2147483648 months -2147483647 days -9223372036854775807 us",
+                Ok("P-178956970Y-8M-2147483647DT-2562047788H-54.775807S"),
+            )],
+        );
+        check(
+            PostgresVerbose,
+            &[(
+                "-2147483648 months -2147483647 days -9223372036854775807 us",
+                Ok("@ 178956970 years 8 mons 2147483647 days 2562047788 hours 54.775807 secs ago"),
+            )],
+        );
+    }
+
+    #[test]
+    fn regress_field_overflow() {
+        // Overflowing each field, with unit aliases, fractional fields and `ago`.
+        check(
+            Postgres,
+            &[
+                ("2147483648 years", Err(FIELD)),
+                ("-2147483649 years", Err(FIELD)),
+                ("2147483648 months", Err(FIELD)),
+                ("-2147483649 months", Err(FIELD)),
+                ("2147483648 days", Err(FIELD)),
+                ("-2147483649 days", Err(FIELD)),
+                ("2562047789 hours", Err(FIELD)),
+                ("-2562047789 hours", Err(FIELD)),
+                ("153722867281 minutes", Err(FIELD)),
+                ("-153722867281 minutes", Err(FIELD)),
+                ("9223372036855 seconds", Err(FIELD)),
+                ("-9223372036855 seconds", Err(FIELD)),
+                ("9223372036854777 millisecond", Err(FIELD)),
+                ("-9223372036854777 millisecond", Err(FIELD)),
+                ("9223372036854775808 microsecond", Err(FIELD)),
+                ("-9223372036854775809 microsecond", Err(FIELD)),
+                ("P2147483648", Err(FIELD)),
+                ("P-2147483649", Err(FIELD)),
+                ("P1-2147483647-2147483647", Err(RANGE)),
+                ("PT2562047789", Err(FIELD)),
+                ("PT-2562047789", Err(FIELD)),
+                ("2147483647 weeks", Err(FIELD)),
+                ("-2147483648 weeks", Err(FIELD)),
+                ("2147483647 decades", Err(FIELD)),
+                ("-2147483648 decades", Err(FIELD)),
+                ("2147483647 centuries", Err(FIELD)),
+                ("-2147483648 centuries", Err(FIELD)),
+                ("2147483647 millennium", Err(FIELD)),
+                ("-2147483648 millennium", Err(FIELD)),
+                ("1 week 2147483647 days", Err(FIELD)),
+                ("-1 week -2147483648 days", Err(FIELD)),
+                ("2147483647 days 1 week", Err(FIELD)),
+                ("-2147483648 days -1 week", Err(FIELD)),
+                ("P1W2147483647D", Err(FIELD)),
+                ("P-1W-2147483648D", Err(FIELD)),
+                ("P2147483647D1W", Err(FIELD)),
+                ("P-2147483648D-1W", Err(FIELD)),
+                ("1 decade 2147483647 years", Err(FIELD)),
+                ("1 century 2147483647 years", Err(FIELD)),
+                ("1 millennium 2147483647 years", Err(FIELD)),
+                ("-1 decade -2147483648 years", Err(FIELD)),
+                ("-1 century -2147483648 years", Err(FIELD)),
+                ("-1 millennium -2147483648 years", Err(FIELD)),
+                ("2147483647 years 1 decade", Err(FIELD)),
+                ("2147483647 years 1 century", Err(FIELD)),
+                ("2147483647 years 1 millennium", Err(FIELD)),
+                ("-2147483648 years -1 decade", Err(FIELD)),
+                ("-2147483648 years -1 century", Err(FIELD)),
+                ("-2147483648 years -1 millennium", Err(FIELD)),
+                ("0.1 millennium 2147483647 months", Err(FIELD)),
+                ("0.1 centuries 2147483647 months", Err(FIELD)),
+                ("0.1 decades 2147483647 months", Err(FIELD)),
+                ("0.1 yrs 2147483647 months", Err(FIELD)),
+                ("-0.1 millennium -2147483648 months", Err(FIELD)),
+                ("-0.1 centuries -2147483648 months", Err(FIELD)),
+                ("-0.1 decades -2147483648 months", Err(FIELD)),
+                ("-0.1 yrs -2147483648 months", Err(FIELD)),
+                ("2147483647 months 0.1 millennium", Err(FIELD)),
+                ("2147483647 months 0.1 centuries", Err(FIELD)),
+                ("2147483647 months 0.1 decades", Err(FIELD)),
+                ("2147483647 months 0.1 yrs", Err(FIELD)),
+                ("-2147483648 months -0.1 millennium", Err(FIELD)),
+                ("-2147483648 months -0.1 centuries", Err(FIELD)),
+                ("-2147483648 months -0.1 decades", Err(FIELD)),
+                ("-2147483648 months -0.1 yrs", Err(FIELD)),
+                ("0.1 months 2147483647 days", Err(FIELD)),
+                ("-0.1 months -2147483648 days", Err(FIELD)),
+                ("2147483647 days 0.1 months", Err(FIELD)),
+                ("-2147483648 days -0.1 months", Err(FIELD)),
+                ("0.5 weeks 2147483647 days", Err(FIELD)),
+                ("-0.5 weeks -2147483648 days", Err(FIELD)),
+                ("2147483647 days 0.5 weeks", Err(FIELD)),
+                ("-2147483648 days -0.5 weeks", Err(FIELD)),
+                ("0.01 months 9223372036854775807 microseconds", Err(FIELD)),
+                ("-0.01 months -9223372036854775808 microseconds", Err(FIELD)),
+                ("9223372036854775807 microseconds 0.01 months", Err(FIELD)),
+                ("-9223372036854775808 microseconds -0.01 months", Err(FIELD)),
+                ("0.1 weeks 9223372036854775807 microseconds", Err(FIELD)),
+                ("-0.1 weeks -9223372036854775808 microseconds", Err(FIELD)),
+                ("9223372036854775807 microseconds 0.1 weeks", Err(FIELD)),
+                ("-9223372036854775808 microseconds -0.1 weeks", Err(FIELD)),
+                ("0.1 days 9223372036854775807 microseconds", Err(FIELD)),
+                ("-0.1 days -9223372036854775808 microseconds", Err(FIELD)),
+                ("9223372036854775807 microseconds 0.1 days", Err(FIELD)),
+                ("-9223372036854775808 microseconds -0.1 days", Err(FIELD)),
+                ("P0.1Y2147483647M", Err(FIELD)),
+                ("P-0.1Y-2147483648M", Err(FIELD)),
+                ("P2147483647M0.1Y", Err(FIELD)),
+                ("P-2147483648M-0.1Y", Err(FIELD)),
+                ("P0.1M2147483647D", Err(FIELD)),
+                ("P-0.1M-2147483648D", Err(FIELD)),
+                ("P2147483647D0.1M", Err(FIELD)),
+                ("P-2147483648D-0.1M", Err(FIELD)),
+                ("P0.5W2147483647D", Err(FIELD)),
+                ("P-0.5W-2147483648D", Err(FIELD)),
+                ("P2147483647D0.5W", Err(FIELD)),
+                ("P-2147483648D-0.5W", Err(FIELD)),
+                ("P0.01MT2562047788H54.775807S", Err(FIELD)),
+                ("P-0.01MT-2562047788H-54.775808S", Err(FIELD)),
+                ("P0.1DT2562047788H54.775807S", Err(FIELD)),
+                ("P-0.1DT-2562047788H-54.775808S", Err(FIELD)),
+                ("PT2562047788.1H54.775807S", Err(FIELD)),
+                ("PT-2562047788.1H-54.775808S", Err(FIELD)),
+                ("PT2562047788H0.1M54.775807S", Err(FIELD)),
+                ("PT-2562047788H-0.1M-54.775808S", Err(FIELD)),
+                ("P0.1-2147483647-00", Err(FIELD)),
+                ("P00-0.1-2147483647", Err(FIELD)),
+                ("P00-0.01-00T2562047788:00:54.775807", Err(FIELD)),
+                ("P00-00-0.1T2562047788:00:54.775807", Err(FIELD)),
+                ("PT2562047788.1:00:54.775807", Err(FIELD)),
+                ("PT2562047788:01.:54.775807", Err(FIELD)),
+                ("0.1 2562047788:0:54.775807", Err(FIELD)),
+                ("0.1 2562047788:0:54.775808 ago", Err(FIELD)),
+                ("2562047788.1:0:54.775807", Err(FIELD)),
+                ("2562047788.1:0:54.775808 ago", Err(FIELD)),
+                ("2562047788:0.1:54.775807", Err(SYNTAX)),
+                ("2562047788:0.1:54.775808 ago", Err(SYNTAX)),
+                ("-2147483648 months ago", Err(FIELD)),
+                ("-2147483648 days ago", Err(FIELD)),
+                ("-9223372036854775808 microseconds ago", Err(FIELD)),
+                (
+                    "-2147483648 months -2147483648 days -9223372036854775808 microseconds ago",
+                    Err(FIELD),
+                ),
+                (
+                    "-2147483648 months -2147483648 days -9223372036854775808 us",
+                    Err(RANGE),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn regress_invalid() {
+        // Misplaced `ago`, dangling units, date keywords and infinity combined with anything else.
+        check(
+            Postgres,
+            &[
+                ("42 days 2 seconds ago ago", Err(SYNTAX)),
+                ("2 minutes ago 5 days", Err(SYNTAX)),
+                ("hour 5 months", Err(SYNTAX)),
+                ("1 year months days 5 hours", Err(SYNTAX)),
+                ("now", Err(SYNTAX)),
+                ("today", Err(SYNTAX)),
+                ("tomorrow", Err(SYNTAX)),
+                ("allballs", Err(SYNTAX)),
+                ("epoch", Err(SYNTAX)),
+                ("yesterday", Err(SYNTAX)),
+                ("infinity years", Err(SYNTAX)),
+                ("infinity ago", Err(SYNTAX)),
+                ("+infinity -infinity", Err(SYNTAX)),
+                ("1 week 1 week", Err(SYNTAX)),
+                ("", Err(SYNTAX)),
+            ],
+        );
+    }
+
+    #[test]
+    fn regress_qualified() {
+        // SQL-spec field restrictions and precisions, which also decide
+        // what a bare number or `mm:ss` means
+        for (input, fields, precision, expected) in [
+            ("999", Second, None, Ok("00:16:39")),
+            ("999", Minute, None, Ok("16:39:00")),
+            ("999", Hour, None, Ok("999:00:00")),
+            ("999", Day, None, Ok("999 days")),
+            ("999", Month, None, Ok("83 years 3 mons")),
+            ("1", Year, None, Ok("1 year")),
+            ("2", Month, None, Ok("2 mons")),
+            ("3", Day, None, Ok("3 days")),
+            ("4", Hour, None, Ok("04:00:00")),
+            ("5", Minute, None, Ok("00:05:00")),
+            ("6", Second, None, Ok("00:00:06")),
+            ("1", YearToMonth, None, Ok("1 mon")),
+            ("1-2", YearToMonth, None, Ok("1 year 2 mons")),
+            ("1 2", DayToHour, None, Ok("1 day 02:00:00")),
+            ("1 2:03", DayToHour, None, Ok("1 day 02:00:00")),
+            ("1 2:03:04", DayToHour, None, Ok("1 day 02:00:00")),
+            ("1 2", DayToMinute, None, Err(SYNTAX)),
+            ("1 2:03", DayToMinute, None, Ok("1 day 02:03:00")),
+            ("1 2:03:04", DayToMinute, None, Ok("1 day 02:03:00")),
+            ("1 2", DayToSecond, None, Err(SYNTAX)),
+            ("1 2:03", DayToSecond, None, Ok("1 day 02:03:00")),
+            ("1 2:03:04", DayToSecond, None, Ok("1 day 02:03:04")),
+            ("1 2", HourToMinute, None, Err(SYNTAX)),
+            ("1 2:03", HourToMinute, None, Ok("1 day 02:03:00")),
+            ("1 2:03:04", HourToMinute, None, Ok("1 day 02:03:00")),
+            ("1 2", HourToSecond, None, Err(SYNTAX)),
+            ("1 2:03", HourToSecond, None, Ok("1 day 02:03:00")),
+            ("1 2:03:04", HourToSecond, None, Ok("1 day 02:03:04")),
+            ("1 2", MinuteToSecond, None, Err(SYNTAX)),
+            ("1 2:03", MinuteToSecond, None, Ok("1 day 00:02:03")),
+            ("1 2:03:04", MinuteToSecond, None, Ok("1 day 02:03:04")),
+            ("1 +2:03", MinuteToSecond, None, Ok("1 day 00:02:03")),
+            ("1 +2:03:04", MinuteToSecond, None, Ok("1 day 02:03:04")),
+            ("1 -2:03", MinuteToSecond, None, Ok("1 day -00:02:03")),
+            ("1 -2:03:04", MinuteToSecond, None, Ok("1 day -02:03:04")),
+            ("123 11", DayToHour, None, Ok("123 days 11:00:00")),
+            ("123 11", Day, None, Err(SYNTAX)),
+            ("123 11", All, None, Err(SYNTAX)),
+            ("123 2:03 -2:04", All, None, Err(SYNTAX)),
+            ("1 day 01:23:45.6789", All, Some(0), Ok("1 day 01:23:46")),
+            ("1 day 01:23:45.6789", All, Some(2), Ok("1 day 01:23:45.68")),
+            ("12:34.5678", MinuteToSecond, Some(2), Ok("00:12:34.57")),
+            ("1.234", Second, None, Ok("00:00:01.234")),
+            ("1.234", Second, Some(2), Ok("00:00:01.23")),
+            ("1 2.345", DayToSecond, Some(2), Err(SYNTAX)),
+            ("1 2:03", DayToSecond, Some(2), Ok("1 day 02:03:00")),
+            ("1 2:03.4567", DayToSecond, Some(2), Ok("1 day 00:02:03.46")),
+            (
+                "1 2:03:04.5678",
+                DayToSecond,
+                Some(2),
+                Ok("1 day 02:03:04.57"),
+            ),
+            ("1 2.345", HourToSecond, Some(2), Err(SYNTAX)),
+            (
+                "1 2:03.45678",
+                HourToSecond,
+                Some(2),
+                Ok("1 day 00:02:03.46"),
+            ),
+            (
+                "1 2:03:04.5678",
+                HourToSecond,
+                Some(2),
+                Ok("1 day 02:03:04.57"),
+            ),
+            ("1 2.3456", MinuteToSecond, Some(2), Err(SYNTAX)),
+            (
+                "1 2:03.5678",
+                MinuteToSecond,
+                Some(2),
+                Ok("1 day 00:02:03.57"),
+            ),
+            (
+                "1 2:03:04.5678",
+                MinuteToSecond,
+                Some(2),
+                Ok("1 day 02:03:04.57"),
+            ),
+            ("2562047788:00:54.775807", Second, Some(2), Err(RANGE)),
+            ("-2562047788:00:54.775807", Second, Some(2), Err(RANGE)),
+        ] {
+            let actual = Interval::parse_qualified(input, Postgres, fields, precision)
+                .map(|interval| interval.to_string())
+                .map_err(|e| e.to_string());
+            assert_eq!(
+                actual,
+                expected
+                    .map(str::to_owned)
+                    .map_err(|kind| message(kind, input))
+            );
+        }
+    }
+
+    #[test]
+    fn iso8601_round_trip() {
+        for (input, iso) in [
+            ("P-1DT2H", "P-1DT2H"),
+            ("-P1D", "P-1D"),
+            ("-P1Y-2M", "P-10M"),
+            ("P0001-02-03T04:05:06", "P1Y2M3DT4H5M6S"),
+            ("-P0001-02-03T04:05:06", "P-1Y-2M-3DT-4H-5M-6S"),
+            ("P1W2D", "P9D"),
+            ("P1.5Y2M", "P1Y8M"),
+            ("PT1.5M", "PT1M30S"),
+            ("P1.5DT1H", "P1DT13H"),
+            ("PT0S", "PT0S"),
+            ("-PT0S", "PT0S"),
+            ("infinity", "infinity"),
+            ("-infinity", "-infinity"),
+        ] {
+            let interval = Interval::parse_iso8601(input).unwrap();
+            assert_eq!(interval.to_iso8601(), iso, "{input}");
+            let reparsed = Interval::parse_iso8601(&interval.to_iso8601()).unwrap();
+            assert!(reparsed.is_identical(&interval), "{input}");
+        }
+    }
+}
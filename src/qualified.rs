@@ -0,0 +1,590 @@
+//! Port of the typmod handling of PostgreSQL's `interval_in`
+//! (`intervaltypmodin` and `AdjustIntervalForTypmod` in
+//! `src/backend/utils/adt/timestamp.c`), for columns declared with fields
+//! and a precision such as `INTERVAL DAY TO SECOND(3)`.
+
+use std::{fmt, marker::PhantomData, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Encode, Postgres, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef},
+};
+
+use crate::{
+    Interval, IntervalStyle, MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_HOUR, USECS_PER_MINUTE, parse,
+};
+
+/// `MAX_INTERVAL_PRECISION`
+const MAX_PRECISION: u8 = 6;
+
+/// The fields of an `INTERVAL fields` type, which decide how the server reads
+/// a bare number or `mm:ss` and which fields it keeps.
+///
+/// Fields to the right of the last one are zeroed, those to the left are
+/// kept, so `INTERVAL MONTH` and `INTERVAL YEAR TO MONTH` hold the same values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntervalFields {
+    /// `INTERVAL YEAR`
+    Year,
+    /// `INTERVAL MONTH`
+    Month,
+    /// `INTERVAL DAY`
+    Day,
+    /// `INTERVAL HOUR`
+    Hour,
+    /// `INTERVAL MINUTE`
+    Minute,
+    /// `INTERVAL SECOND`
+    Second,
+    /// `INTERVAL YEAR TO MONTH`
+    YearToMonth,
+    /// `INTERVAL DAY TO HOUR`
+    DayToHour,
+    /// `INTERVAL DAY TO MINUTE`
+    DayToMinute,
+    /// `INTERVAL DAY TO SECOND`
+    DayToSecond,
+    /// `INTERVAL HOUR TO MINUTE`
+    HourToMinute,
+    /// `INTERVAL HOUR TO SECOND`
+    HourToSecond,
+    /// `INTERVAL MINUTE TO SECOND`
+    MinuteToSecond,
+    /// A plain `INTERVAL` (`INTERVAL_FULL_RANGE`)
+    #[default]
+    All,
+}
+
+impl Interval {
+    /// Parse `s` like `interval_in` does for a column of type
+    /// `INTERVAL fields(precision)`, where `None` is no precision.
+    ///
+    /// The fields decide what a bare number means (`'1 2'` is `1 day 02:00:00`
+    /// as `DAY TO HOUR` but invalid as a plain `INTERVAL`), and
+    /// `mm:ss` is read as minutes and seconds for `MINUTE TO SECOND`. The
+    /// result is then adjusted as by [`Interval::qualify`].
+    pub fn parse_qualified(
+        s: &str,
+        style: IntervalStyle,
+        fields: IntervalFields,
+        precision: Option<u8>,
+    ) -> Result<Self, BoxDynError> {
+        parse::parse_interval(s, style, fields)?.qualify(fields, precision)
+    }
+
+    /// `AdjustIntervalForTypmod`: zero the fields to the right of the last of
+    /// `fields` (`INTERVAL DAY TO HOUR` truncates `1 day 02:03:04` to
+    /// `1 day 02:00:00`) and round the seconds to `precision` fractional
+    /// digits, half away from zero. The infinities are left as they are.
+    pub fn qualify(
+        &self,
+        fields: IntervalFields,
+        precision: Option<u8>,
+    ) -> Result<Self, BoxDynError> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let mut result = self.clone();
+        match fields {
+            IntervalFields::Year => {
+                result.months = result.months / MONTHS_PER_YEAR as i32 * MONTHS_PER_YEAR as i32;
+                result.days = 0;
+                result.microseconds = 0;
+            }
+            IntervalFields::Month | IntervalFields::YearToMonth => {
+                result.days = 0;
+                result.microseconds = 0;
+            }
+            IntervalFields::Day => result.microseconds = 0,
+            IntervalFields::Hour | IntervalFields::DayToHour => {
+                result.microseconds = result.microseconds / USECS_PER_HOUR * USECS_PER_HOUR;
+            }
+            IntervalFields::Minute | IntervalFields::DayToMinute | IntervalFields::HourToMinute => {
+                result.microseconds = result.microseconds / USECS_PER_MINUTE * USECS_PER_MINUTE;
+            }
+            IntervalFields::Second
+            | IntervalFields::DayToSecond
+            | IntervalFields::HourToSecond
+            | IntervalFields::MinuteToSecond
+            | IntervalFields::All => {}
+        }
+
+        match precision {
+            Some(precision) => result.round_to_precision(precision),
+            None => Ok(result),
+        }
+    }
+
+    /// Round the seconds to `precision` fractional digits, half away from
+    /// zero, like storing the value in an `interval(precision)` column:
+    /// `00:00:01.2345` becomes `00:00:01.235` with a precision of 3. Months
+    /// and days are kept, and the infinities are left as they are.
+    pub fn round_to_precision(&self, precision: u8) -> Result<Self, BoxDynError> {
+        let scale = precision_scale(precision)?;
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let offset = scale / 2;
+        let rounded = if self.microseconds >= 0 {
+            self.microseconds.checked_add(offset)
+        } else {
+            self.microseconds.checked_sub(offset)
+        }
+        .ok_or(OUT_OF_RANGE)?;
+        Ok(Self {
+            microseconds: rounded - rounded % scale,
+            ..self.clone()
+        })
+    }
+
+    /// Drop the fractional digits of the seconds beyond `precision`, rounding
+    /// toward zero: `00:00:01.2345` becomes `00:00:01.234` with a precision
+    /// of 3.
+    pub fn trunc_to_precision(&self, precision: u8) -> Result<Self, BoxDynError> {
+        let scale = precision_scale(precision)?;
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        Ok(Self {
+            microseconds: self.microseconds - self.microseconds % scale,
+            ..self.clone()
+        })
+    }
+
+    /// Serialize the value rounded to `PRECISION` fractional digits with
+    /// [`Interval::round_to_precision`], for
+    /// `#[serde(serialize_with = "Interval::serialize_with_precision::<3, _>")]`.
+    ///
+    /// The string is PostgreSQL's `iso_8601` output, so it has at most
+    /// `PRECISION` fractional digits, e.g. `PT1.235S`.
+    pub fn serialize_with_precision<const PRECISION: u8, S>(
+        value: &Interval,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let rounded = value
+            .round_to_precision(PRECISION)
+            .map_err(serde::ser::Error::custom)?;
+        #[cfg(not(feature = "serde-struct"))]
+        return serializer.collect_str(&rounded.display(IntervalStyle::Iso8601));
+        #[cfg(feature = "serde-struct")]
+        return rounded.serialize(serializer);
+    }
+}
+
+/// `IntervalScales[precision]`: the microseconds in the last digit kept.
+fn precision_scale(precision: u8) -> Result<i64, BoxDynError> {
+    if precision > MAX_PRECISION {
+        return Err(format!(
+            "interval({precision}) precision must be between 0 and {MAX_PRECISION}"
+        )
+        .into());
+    }
+    Ok(10_i64.pow(u32::from(MAX_PRECISION - precision)))
+}
+
+/// Type-level [`IntervalFields`], implemented by the types in [`fields`], for
+/// [`QualifiedInterval`].
+pub trait IntervalQualifier {
+    const FIELDS: IntervalFields;
+}
+
+/// Marker types naming the [`IntervalFields`] of a [`QualifiedInterval`].
+pub mod fields {
+    use super::{IntervalFields, IntervalQualifier};
+
+    macro_rules! qualifiers {
+        ($($name:ident => $sql:literal,)*) => {
+            $(
+                #[doc = concat!("`", $sql, "`")]
+                #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+                pub struct $name;
+
+                impl IntervalQualifier for $name {
+                    const FIELDS: IntervalFields = IntervalFields::$name;
+                }
+            )*
+        };
+    }
+
+    qualifiers! {
+        Year => "INTERVAL YEAR",
+        Month => "INTERVAL MONTH",
+        Day => "INTERVAL DAY",
+        Hour => "INTERVAL HOUR",
+        Minute => "INTERVAL MINUTE",
+        Second => "INTERVAL SECOND",
+        YearToMonth => "INTERVAL YEAR TO MONTH",
+        DayToHour => "INTERVAL DAY TO HOUR",
+        DayToMinute => "INTERVAL DAY TO MINUTE",
+        DayToSecond => "INTERVAL DAY TO SECOND",
+        HourToMinute => "INTERVAL HOUR TO MINUTE",
+        HourToSecond => "INTERVAL HOUR TO SECOND",
+        MinuteToSecond => "INTERVAL MINUTE TO SECOND",
+        All => "INTERVAL",
+    }
+}
+
+/// An [`Interval`] that always holds what a column of type
+/// `INTERVAL F(PRECISION)` would store, e.g.
+/// `QualifiedInterval<fields::DayToSecond, 3>` for `INTERVAL DAY TO SECOND(3)`.
+///
+/// Every way of making one (construction, parsing, deserializing and
+/// decoding) reads and adjusts the value like the server does for that type;
+/// see [`Interval::parse_qualified`]. It dereferences to the `Interval`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QualifiedInterval<F, const PRECISION: u8 = 6> {
+    interval: Interval,
+    fields: PhantomData<F>,
+}
+
+/// `INTERVAL YEAR TO MONTH`
+pub type YearMonthInterval = QualifiedInterval<fields::YearToMonth>;
+
+/// `INTERVAL DAY TO SECOND(PRECISION)`
+pub type DayTimeInterval<const PRECISION: u8 = 6> =
+    QualifiedInterval<fields::DayToSecond, PRECISION>;
+
+impl<F: IntervalQualifier, const PRECISION: u8> QualifiedInterval<F, PRECISION> {
+    /// Truncate and round `interval` to the type's fields and precision, like
+    /// assigning it to such a column.
+    pub fn new(interval: Interval) -> Result<Self, BoxDynError> {
+        const {
+            assert!(
+                PRECISION <= MAX_PRECISION,
+                "interval precision must be between 0 and 6"
+            )
+        };
+        Ok(Self {
+            interval: interval.qualify(F::FIELDS, Some(PRECISION))?,
+            fields: PhantomData,
+        })
+    }
+
+    /// Parse `s` like `interval_in` does for this type in a session using the
+    /// given `IntervalStyle`.
+    pub fn parse_with_style(s: &str, style: IntervalStyle) -> Result<Self, BoxDynError> {
+        Self::new(parse::parse_interval(s, style, F::FIELDS)?)
+    }
+
+    /// `Decode` on the raw bytes of a value in `format`.
+    pub(crate) fn decode_bytes(format: PgValueFormat, bytes: &[u8]) -> Result<Self, BoxDynError> {
+        Self::new(Interval::decode_bytes(format, bytes)?)
+    }
+
+    pub fn into_inner(self) -> Interval {
+        self.interval
+    }
+}
+
+impl<F, const PRECISION: u8> Deref for QualifiedInterval<F, PRECISION> {
+    type Target = Interval;
+
+    fn deref(&self) -> &Interval {
+        &self.interval
+    }
+}
+
+impl<F, const PRECISION: u8> From<QualifiedInterval<F, PRECISION>> for Interval {
+    fn from(value: QualifiedInterval<F, PRECISION>) -> Self {
+        value.interval
+    }
+}
+
+impl<F: IntervalQualifier, const PRECISION: u8> TryFrom<Interval>
+    for QualifiedInterval<F, PRECISION>
+{
+    type Error = BoxDynError;
+
+    fn try_from(value: Interval) -> Result<Self, BoxDynError> {
+        Self::new(value)
+    }
+}
+
+impl<F: IntervalQualifier, const PRECISION: u8> FromStr for QualifiedInterval<F, PRECISION> {
+    type Err = BoxDynError;
+
+    /// Parse with the default `IntervalStyle`; see
+    /// [`QualifiedInterval::parse_with_style`].
+    fn from_str(s: &str) -> Result<Self, BoxDynError> {
+        Self::parse_with_style(s, IntervalStyle::Postgres)
+    }
+}
+
+impl<F, const PRECISION: u8> fmt::Display for QualifiedInterval<F, PRECISION> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.interval.fmt(f)
+    }
+}
+
+impl<F, const PRECISION: u8> Serialize for QualifiedInterval<F, PRECISION> {
+    /// Serialize with [`Interval::serialize_with_precision`], so strings have
+    /// at most `PRECISION` fractional digits.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Interval::serialize_with_precision::<PRECISION, S>(&self.interval, serializer)
+    }
+}
+
+impl<'de, F: IntervalQualifier, const PRECISION: u8> Deserialize<'de>
+    for QualifiedInterval<F, PRECISION>
+{
+    /// Deserialize like [`Interval`] does, reading strings the way the server
+    /// reads them for this type.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[cfg(not(feature = "serde-struct"))]
+        let value = parse::parse_deserialized(&String::deserialize(deserializer)?, F::FIELDS)
+            .and_then(Self::new);
+        #[cfg(feature = "serde-struct")]
+        let value = Self::new(Interval::deserialize(deserializer)?);
+        value.map_err(serde::de::Error::custom)
+    }
+}
+
+/// Exported as `Interval`, the type it serializes as.
+#[cfg(feature = "ts-rs")]
+impl<F: 'static, const PRECISION: u8> ts_rs::TS for QualifiedInterval<F, PRECISION> {
+    type WithoutGenerics = Interval;
+
+    fn name() -> String {
+        Interval::name()
+    }
+
+    fn inline() -> String {
+        Interval::inline()
+    }
+
+    fn inline_flattened() -> String {
+        Interval::inline_flattened()
+    }
+
+    fn decl() -> String {
+        Interval::decl()
+    }
+
+    fn decl_concrete() -> String {
+        Interval::decl_concrete()
+    }
+
+    fn visit_dependencies(v: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        v.visit::<Interval>();
+    }
+
+    fn output_path() -> Option<&'static std::path::Path> {
+        Interval::output_path()
+    }
+}
+
+impl<F, const PRECISION: u8> Type<Postgres> for QualifiedInterval<F, PRECISION> {
+    fn type_info() -> PgTypeInfo {
+        Interval::type_info()
+    }
+}
+
+impl<F, const PRECISION: u8> PgHasArrayType for QualifiedInterval<F, PRECISION> {
+    fn array_type_info() -> PgTypeInfo {
+        Interval::array_type_info()
+    }
+}
+
+impl<'de, F: IntervalQualifier, const PRECISION: u8> Decode<'de, Postgres>
+    for QualifiedInterval<F, PRECISION>
+{
+    fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
+        Self::decode_bytes(value.format(), value.as_bytes()?)
+    }
+}
+
+impl<F, const PRECISION: u8> Encode<'_, Postgres> for QualifiedInterval<F, PRECISION> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.interval.encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.interval.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::postgres::PgValueFormat;
+
+    use crate::{DayTimeInterval, Interval, QualifiedInterval, YearMonthInterval, fields};
+
+    fn seconds(s: &str) -> Interval {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_and_trunc_to_precision() {
+        // (input, precision, `interval(precision)` on the server, truncated)
+        for (input, precision, rounded, truncated) in [
+            ("1.2345 sec", 3, "00:00:01.235", "00:00:01.234"),
+            ("-1.2345 sec", 3, "-00:00:01.235", "-00:00:01.234"),
+            ("0.000015 sec", 5, "00:00:00.00002", "00:00:00.00001"),
+            ("-0.000015 sec", 5, "-00:00:00.00002", "-00:00:00.00001"),
+            // read as -0.000001
+            ("-0.0000015 sec", 5, "00:00:00", "00:00:00"),
+            ("2.5 sec", 0, "00:00:03", "00:00:02"),
+            ("-2.5 sec", 0, "-00:00:03", "-00:00:02"),
+            ("-00:00:00.5", 0, "-00:00:01", "00:00:00"),
+            ("1 mon 1.999999 sec", 0, "1 mon 00:00:02", "1 mon 00:00:01"),
+            (
+                "1 day -0.4999995 sec",
+                6,
+                "1 day -00:00:00.499999",
+                "1 day -00:00:00.499999",
+            ),
+        ] {
+            let interval = seconds(input);
+            let actual = interval.round_to_precision(precision).unwrap();
+            assert_eq!(
+                actual.to_string(),
+                rounded,
+                "{input} rounded to {precision}"
+            );
+            let actual = interval.trunc_to_precision(precision).unwrap();
+            assert_eq!(
+                actual.to_string(),
+                truncated,
+                "{input} truncated to {precision}"
+            );
+        }
+
+        let error = seconds("1 sec").round_to_precision(7).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "interval(7) precision must be between 0 and 6"
+        );
+        let error = seconds("1 sec").trunc_to_precision(u8::MAX).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "interval(255) precision must be between 0 and 6"
+        );
+        assert!(Interval::INFINITY.round_to_precision(7).is_err());
+        let rounded = Interval::NEG_INFINITY.round_to_precision(0).unwrap();
+        assert!(rounded.is_neg_infinity());
+
+        // rounding away from zero past the largest microseconds
+        let error = seconds("2562047788:00:54.775807")
+            .round_to_precision(0)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "interval out of range");
+        let truncated = seconds("2562047788:00:54.775807")
+            .trunc_to_precision(0)
+            .unwrap();
+        assert_eq!(truncated.to_string(), "2562047788:00:54");
+    }
+
+    #[test]
+    fn serialize_with_precision() {
+        fn to_json<const PRECISION: u8>(interval: &Interval) -> String {
+            let mut json = Vec::new();
+            let mut serializer = serde_json::Serializer::new(&mut json);
+            Interval::serialize_with_precision::<PRECISION, _>(interval, &mut serializer).unwrap();
+            String::from_utf8(json).unwrap()
+        }
+
+        let interval = seconds("1 day -1.2345 sec");
+        let rounded = interval.round_to_precision(3).unwrap();
+        assert!(rounded.is_identical(&Interval::new(0, 1, -1_235_000)));
+        #[cfg(not(feature = "serde-struct"))]
+        {
+            assert_eq!(to_json::<3>(&interval), r#""P1DT-1.235S""#);
+            assert_eq!(to_json::<0>(&interval), r#""P1DT-1S""#);
+            assert_eq!(to_json::<6>(&interval), r#""P1DT-1.2345S""#);
+            assert_eq!(to_json::<0>(&seconds("0.5 sec")), r#""PT1S""#);
+            assert_eq!(to_json::<0>(&Interval::INFINITY), r#""infinity""#);
+            let value = DayTimeInterval::<2>::new(seconds("1.005 sec")).unwrap();
+            assert_eq!(serde_json::to_string(&value).unwrap(), r#""PT1.01S""#);
+        }
+        #[cfg(feature = "serde-struct")]
+        assert_eq!(
+            to_json::<3>(&interval),
+            r#"{"months":0,"days":1,"microseconds":-1235000}"#
+        );
+    }
+
+    #[test]
+    fn decode_applies_fields_and_precision() {
+        let text = b"1 year 2 mons 3 days 04:05:06.789";
+        let decoded = YearMonthInterval::decode_bytes(PgValueFormat::Text, text).unwrap();
+        assert!(decoded.is_identical(&Interval::new(14, 0, 0)));
+        let decoded = DayTimeInterval::<1>::decode_bytes(PgValueFormat::Text, text).unwrap();
+        assert!(decoded.is_identical(&Interval::new(14, 3, 14_706_800_000)));
+        // `sql_standard` output, rounded half away from zero
+        let text = b"-1-2 +3 -4:05:06.5";
+        let decoded = DayTimeInterval::<0>::decode_bytes(PgValueFormat::Text, text).unwrap();
+        assert!(decoded.is_identical(&Interval::new(-14, 3, -14_707_000_000)));
+
+        let binary = [
+            &(-14_706_500_000_i64).to_be_bytes()[..],
+            &3_i32.to_be_bytes(),
+            &(-14_i32).to_be_bytes(),
+        ]
+        .concat();
+        let decoded = DayTimeInterval::<0>::decode_bytes(PgValueFormat::Binary, &binary).unwrap();
+        assert!(decoded.is_identical(&Interval::new(-14, 3, -14_707_000_000)));
+        let decoded = YearMonthInterval::decode_bytes(PgValueFormat::Binary, &binary).unwrap();
+        assert!(decoded.is_identical(&Interval::new(-14, 0, 0)));
+        let decoded =
+            QualifiedInterval::<fields::Year>::decode_bytes(PgValueFormat::Binary, &binary)
+                .unwrap();
+        assert!(decoded.is_identical(&Interval::new(-12, 0, 0)));
+    }
+
+    #[cfg(not(feature = "serde-struct"))]
+    #[test]
+    fn deserialize_applies_fields_and_precision() {
+        fn from_json<T: serde::de::DeserializeOwned>(s: &str) -> Interval
+        where
+            Interval: From<T>,
+        {
+            serde_json::from_str::<T>(&format!("\"{s}\""))
+                .unwrap()
+                .into()
+        }
+
+        let server = "1 year 2 mons 3 days 04:05:06.789";
+        assert!(from_json::<YearMonthInterval>(server).is_identical(&Interval::new(14, 0, 0)));
+        let interval = from_json::<DayTimeInterval<1>>(server);
+        assert!(interval.is_identical(&Interval::new(14, 3, 14_706_800_000)));
+        let interval = from_json::<DayTimeInterval<0>>("-1-2 +3 -4:05:06.5");
+        assert!(interval.is_identical(&Interval::new(-14, 3, -14_707_000_000)));
+        // the fields decide what a bare number means
+        let interval = from_json::<QualifiedInterval<fields::DayToHour>>("1 2");
+        assert!(interval.is_identical(&Interval::new(0, 1, 7_200_000_000)));
+        assert!(serde_json::from_str::<Interval>("\"1 2\"").is_err());
+
+        // ISO 8601 with a leading sign
+        assert!(from_json::<DayTimeInterval>("-P1D").is_identical(&Interval::new(0, -1, 0)));
+        let interval = from_json::<DayTimeInterval<3>>("-P1DT2H3.4567S");
+        assert!(interval.is_identical(&Interval::new(0, -1, -7_203_457_000)));
+        let interval = from_json::<YearMonthInterval>("+P1Y2M3DT4H");
+        assert!(interval.is_identical(&Interval::new(14, 0, 0)));
+        assert!(from_json::<DayTimeInterval>("-infinity").is_neg_infinity());
+    }
+
+    #[cfg(feature = "serde-struct")]
+    #[test]
+    fn deserialize_applies_fields_and_precision() {
+        let json = r#"{"months":14,"days":3,"microseconds":14706789500}"#;
+        let interval = serde_json::from_str::<YearMonthInterval>(json).unwrap();
+        assert!(interval.is_identical(&Interval::new(14, 0, 0)));
+        let interval = serde_json::from_str::<DayTimeInterval<3>>(json).unwrap();
+        assert!(interval.is_identical(&Interval::new(14, 3, 14_706_790_000)));
+    }
+}
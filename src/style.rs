@@ -0,0 +1,18 @@
+/// The PostgreSQL `IntervalStyle` setting, which selects the text format
+/// the server uses for `INTERVAL` output (and, for [`IntervalStyle::SqlStandard`],
+/// how ambiguous signs in input are read).
+///
+/// See also:
+///   - https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVALSTYLE-OUTPUT-TABLE
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntervalStyle {
+    /// `1 year 2 mons 3 days 04:05:06` (the server default)
+    #[default]
+    Postgres,
+    /// `@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs`
+    PostgresVerbose,
+    /// `+1-2 +3 +4:05:06`
+    SqlStandard,
+    /// `P1Y2M3DT4H5M6S`
+    Iso8601,
+}
@@ -0,0 +1,1017 @@
+//! Port of PostgreSQL's `to_char(interval, text)` (`interval_to_char` and
+//! `DCH_to_char` in `src/backend/utils/adt/formatting.c`), and its reverse.
+
+use sqlx::error::BoxDynError;
+
+use crate::{
+    DAYS_PER_MONTH, Interval, MONTHS_PER_YEAR, OUT_OF_RANGE, USECS_PER_HOUR, USECS_PER_MINUTE,
+    USECS_PER_SEC,
+};
+
+/// `HOURS_PER_DAY`
+const HOURS_PER_DAY: i64 = 24;
+
+/// `rm_months_upper`, December first.
+const RM_MONTHS: [&str; 12] = [
+    "XII", "XI", "X", "IX", "VIII", "VII", "VI", "V", "IV", "III", "II", "I",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    /// `AM`/`PM`, in upper or lower case and with or without dots
+    Meridiem {
+        upper: bool,
+        dots: bool,
+    },
+    Cc,
+    Ddd,
+    Dd,
+    /// `FF1` to `FF6`
+    Ff(u8),
+    Fx,
+    Hh,
+    Hh24,
+    Mi,
+    Mm,
+    Ms,
+    Q,
+    Rm {
+        upper: bool,
+    },
+    Ssss,
+    Ss,
+    Us,
+    Ww,
+    W,
+    YComma,
+    Yyyy,
+    Yyy,
+    Yy,
+    Y,
+    /// Names, eras and time zones: `INVALID_FOR_INTERVAL`
+    Invalid,
+    /// ISO week dates and Julian days, which the server computes as if the
+    /// fields were a date
+    DateOnly,
+}
+
+/// `DCH_keywords`, searched in order for the first one the template starts
+/// with.
+const KEYWORDS: &[(&str, Key)] = {
+    use Key::*;
+    const AM: Key = Meridiem {
+        upper: true,
+        dots: false,
+    };
+    const AM_DOTS: Key = Meridiem {
+        upper: true,
+        dots: true,
+    };
+    const AM_LOWER: Key = Meridiem {
+        upper: false,
+        dots: false,
+    };
+    const AM_LOWER_DOTS: Key = Meridiem {
+        upper: false,
+        dots: true,
+    };
+    &[
+        ("A.D.", Invalid),
+        ("A.M.", AM_DOTS),
+        ("AD", Invalid),
+        ("AM", AM),
+        ("B.C.", Invalid),
+        ("BC", Invalid),
+        ("CC", Cc),
+        ("DAY", Invalid),
+        ("DDD", Ddd),
+        ("DD", Dd),
+        ("DY", Invalid),
+        ("Day", Invalid),
+        ("Dy", Invalid),
+        ("D", Invalid),
+        ("FF1", Ff(1)),
+        ("FF2", Ff(2)),
+        ("FF3", Ff(3)),
+        ("FF4", Ff(4)),
+        ("FF5", Ff(5)),
+        ("FF6", Ff(6)),
+        ("FX", Fx),
+        ("HH24", Hh24),
+        ("HH12", Hh),
+        ("HH", Hh),
+        ("IDDD", DateOnly),
+        ("ID", Invalid),
+        ("IW", DateOnly),
+        ("IYYY", DateOnly),
+        ("IYY", DateOnly),
+        ("IY", DateOnly),
+        ("I", DateOnly),
+        ("J", DateOnly),
+        ("MI", Mi),
+        ("MM", Mm),
+        ("MONTH", Invalid),
+        ("MON", Invalid),
+        ("MS", Ms),
+        ("Month", Invalid),
+        ("Mon", Invalid),
+        ("OF", Invalid),
+        ("P.M.", AM_DOTS),
+        ("PM", AM),
+        ("Q", Q),
+        ("RM", Rm { upper: true }),
+        ("SSSSS", Ssss),
+        ("SSSS", Ssss),
+        ("SS", Ss),
+        ("TZH", Invalid),
+        ("TZM", Invalid),
+        ("TZ", Invalid),
+        ("US", Us),
+        ("WW", Ww),
+        ("W", W),
+        ("Y,YYY", YComma),
+        ("YYYY", Yyyy),
+        ("YYY", Yyy),
+        ("YY", Yy),
+        ("Y", Y),
+        ("a.d.", Invalid),
+        ("a.m.", AM_LOWER_DOTS),
+        ("ad", Invalid),
+        ("am", AM_LOWER),
+        ("b.c.", Invalid),
+        ("bc", Invalid),
+        ("cc", Cc),
+        ("day", Invalid),
+        ("ddd", Ddd),
+        ("dd", Dd),
+        ("dy", Invalid),
+        ("d", Invalid),
+        ("ff1", Ff(1)),
+        ("ff2", Ff(2)),
+        ("ff3", Ff(3)),
+        ("ff4", Ff(4)),
+        ("ff5", Ff(5)),
+        ("ff6", Ff(6)),
+        ("fx", Fx),
+        ("hh24", Hh24),
+        ("hh12", Hh),
+        ("hh", Hh),
+        ("iddd", DateOnly),
+        ("id", Invalid),
+        ("iw", DateOnly),
+        ("iyyy", DateOnly),
+        ("iyy", DateOnly),
+        ("iy", DateOnly),
+        ("i", DateOnly),
+        ("j", DateOnly),
+        ("mi", Mi),
+        ("mm", Mm),
+        ("month", Invalid),
+        ("mon", Invalid),
+        ("ms", Ms),
+        ("of", Invalid),
+        ("p.m.", AM_LOWER_DOTS),
+        ("pm", AM_LOWER),
+        ("q", Q),
+        ("rm", Rm { upper: false }),
+        ("sssss", Ssss),
+        ("ssss", Ssss),
+        ("ss", Ss),
+        ("tzh", Invalid),
+        ("tzm", Invalid),
+        ("tz", Invalid),
+        ("us", Us),
+        ("ww", Ww),
+        ("w", W),
+        ("y,yyy", YComma),
+        ("yyyy", Yyyy),
+        ("yyy", Yyy),
+        ("yy", Yy),
+        ("y", Y),
+    ]
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ordinal {
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node<'a> {
+    Action {
+        key: Key,
+        pattern: &'a str,
+        /// `FM`: no padding, and variable width when parsing
+        fill: bool,
+        /// `TH`/`th`
+        ordinal: Option<Ordinal>,
+    },
+    /// Quoted text and letters, which must be matched exactly
+    Char(char),
+    /// `NODE_TYPE_SEPARATOR` and `NODE_TYPE_SPACE`
+    Separator(char),
+}
+
+/// `is_separator_char`: printable ASCII other than letters and digits.
+fn is_separator_char(c: char) -> bool {
+    c.is_ascii_graphic() && !c.is_ascii_alphanumeric()
+}
+
+/// `parse_format` for the `DCH_` patterns.
+fn parse_template(mut template: &str) -> Vec<Node<'_>> {
+    let mut nodes = Vec::new();
+    while !template.is_empty() {
+        let mut fill = false;
+        for (prefix, is_fill) in [("FM", true), ("fm", true), ("TM", false), ("tm", false)] {
+            if let Some(rest) = template.strip_prefix(prefix) {
+                fill = is_fill;
+                template = rest;
+                break;
+            }
+        }
+
+        if let Some(&(pattern, key)) = KEYWORDS.iter().find(|(k, _)| template.starts_with(k)) {
+            template = &template[pattern.len()..];
+            let mut ordinal = None;
+            for (suffix, kind) in [
+                ("TH", Some(Ordinal::Upper)),
+                ("th", Some(Ordinal::Lower)),
+                ("SP", None),
+            ] {
+                if let Some(rest) = template.strip_prefix(suffix) {
+                    ordinal = kind;
+                    template = rest;
+                    break;
+                }
+            }
+            nodes.push(Node::Action {
+                key,
+                pattern,
+                fill,
+                ordinal,
+            });
+        } else if let Some(quoted) = template.strip_prefix('"') {
+            let mut chars = quoted.chars();
+            loop {
+                match chars.next() {
+                    None => break,
+                    Some('"') => break,
+                    // a backslash quotes the next character, if any
+                    Some('\\') if !chars.as_str().is_empty() => {
+                        nodes.push(Node::Char(chars.next().unwrap()));
+                    }
+                    Some(c) => nodes.push(Node::Char(c)),
+                }
+            }
+            template = chars.as_str();
+        } else if !template.is_empty() {
+            // outside quotes, a backslash only escapes a double quote
+            if template.starts_with("\\\"") {
+                template = &template[1..];
+            }
+            let c = template.chars().next().unwrap();
+            template = &template[c.len_utf8()..];
+            nodes.push(if is_separator_char(c) || c.is_whitespace() {
+                Node::Separator(c)
+            } else {
+                Node::Char(c)
+            });
+        }
+    }
+    nodes
+}
+
+/// `str_numth`: append `ST`, `ND`, `RD` or `TH` to a number.
+fn push_ordinal(out: &mut String, start: usize, ordinal: Option<Ordinal>) {
+    let Some(ordinal) = ordinal else {
+        return;
+    };
+    let num = &out.as_bytes()[start..];
+    let last = num[num.len() - 1];
+    // the teens all take `TH`
+    let teen = num.len() > 1 && num[num.len() - 2] == b'1';
+    let suffix = match (teen, last) {
+        (false, b'1') => "ST",
+        (false, b'2') => "ND",
+        (false, b'3') => "RD",
+        _ => "TH",
+    };
+    match ordinal {
+        Ordinal::Upper => out.push_str(suffix),
+        Ordinal::Lower => out.push_str(&suffix.to_ascii_lowercase()),
+    }
+}
+
+/// `struct fmt_tm` and the fraction, as `interval_to_char` fills them.
+struct Tm {
+    year: i32,
+    mon: i32,
+    mday: i32,
+    hour: i64,
+    min: i32,
+    sec: i32,
+    fsec: i32,
+    /// the total span in days, with 30-day months
+    yday: i32,
+}
+
+impl Tm {
+    fn new(interval: &Interval) -> Self {
+        let months = interval.months;
+        let time = interval.microseconds;
+        let year = months / MONTHS_PER_YEAR as i32;
+        let mon = months % MONTHS_PER_YEAR as i32;
+        let mday = interval.days;
+        Self {
+            year,
+            mon,
+            mday,
+            hour: time / USECS_PER_HOUR,
+            min: (time % USECS_PER_HOUR / USECS_PER_MINUTE) as i32,
+            sec: (time % USECS_PER_MINUTE / USECS_PER_SEC) as i32,
+            fsec: (time % USECS_PER_SEC) as i32,
+            // `int` arithmetic in the server, which wraps on overflow
+            yday: year
+                .wrapping_mul(MONTHS_PER_YEAR as i32)
+                .wrapping_add(mon)
+                .wrapping_mul(DAYS_PER_MONTH)
+                .wrapping_add(mday),
+        }
+    }
+}
+
+/// The width of the zero-padded number a pattern writes, one more for a
+/// negative number where the server pads those wider.
+fn width(fill: bool, value: i64, positive: usize, negative: usize) -> usize {
+    match (fill, value >= 0) {
+        (true, _) => 0,
+        (false, true) => positive,
+        (false, false) => negative,
+    }
+}
+
+/// The digits [`Interval::parse_with`] reads for a pattern followed by
+/// another one, the width `format` pads it to.
+fn fixed_digits(key: Key) -> Option<usize> {
+    Some(match key {
+        Key::Hh | Key::Hh24 | Key::Mi | Key::Ss | Key::Mm | Key::Dd => 2,
+        Key::Ms => 3,
+        Key::Us => 6,
+        Key::Ff(n) => n.into(),
+        Key::Yyyy => 4,
+        // the digits after the comma
+        Key::YComma => 3,
+        _ => return None,
+    })
+}
+
+impl Interval {
+    /// `to_char(interval, template)`: write the interval using PostgreSQL's
+    /// template patterns, e.g. `HH24:MI:SS` for `27:03:04` or
+    /// `DD "days" HH24:MI` for `3 days 04:05`.
+    ///
+    /// Each pattern writes its field of the interval broken down like
+    /// [`Display`](std::fmt::Display) does, with its own sign: `HH24` is the
+    /// hours of the time, which may exceed 24, `DD` the days, `MM` the months
+    /// left over after whole years, `YYYY` the years, `MS`, `US` and
+    /// `FF1`–`FF6` the fraction of the seconds, `SSSS` the whole seconds of
+    /// the time and `DDD` the total span in days, with 30-day months. `HH`
+    /// and `HH12` show the hours on a 12-hour clock, with `AM`/`PM` telling
+    /// them apart; `FM` and `TH` work as on the server, and text in double
+    /// quotes is copied as is.
+    ///
+    /// Patterns that need a date fail like on the server: month and day
+    /// names, eras and time zones with `invalid format specification for an
+    /// interval value`, and, unlike the server, which treats the fields as a
+    /// date, the ISO week-date patterns (`IYYY`, `IW`, ...) and `J`.
+    ///
+    /// `None` is SQL `NULL`, which the server returns for an empty template
+    /// and for the infinities.
+    pub fn format(&self, template: &str) -> Result<Option<String>, BoxDynError> {
+        if template.is_empty() || !self.is_finite() {
+            return Ok(None);
+        }
+        let tm = Tm::new(self);
+        let mut out = String::new();
+        for node in parse_template(template) {
+            let (key, pattern, fill, ordinal) = match node {
+                Node::Char(c) | Node::Separator(c) => {
+                    out.push(c);
+                    continue;
+                }
+                Node::Action {
+                    key,
+                    pattern,
+                    fill,
+                    ordinal,
+                } => (key, pattern, fill, ordinal),
+            };
+            let start = out.len();
+            match key {
+                Key::Meridiem { upper, dots } => {
+                    let pm = tm.hour % HOURS_PER_DAY >= HOURS_PER_DAY / 2;
+                    out.push_str(match (pm, upper, dots) {
+                        (false, true, true) => "A.M.",
+                        (false, true, false) => "AM",
+                        (false, false, true) => "a.m.",
+                        (false, false, false) => "am",
+                        (true, true, true) => "P.M.",
+                        (true, true, false) => "PM",
+                        (true, false, true) => "p.m.",
+                        (true, false, false) => "pm",
+                    });
+                    continue;
+                }
+                Key::Hh => {
+                    let hour = match tm.hour % (HOURS_PER_DAY / 2) {
+                        0 => HOURS_PER_DAY / 2,
+                        hour => hour,
+                    };
+                    out.push_str(&format!("{hour:0w$}", w = width(fill, tm.hour, 2, 3)));
+                }
+                Key::Hh24 => {
+                    out.push_str(&format!("{:0w$}", tm.hour, w = width(fill, tm.hour, 2, 3)));
+                }
+                Key::Mi => {
+                    let w = width(fill, tm.min.into(), 2, 3);
+                    out.push_str(&format!("{:0w$}", tm.min));
+                }
+                Key::Ss => {
+                    let w = width(fill, tm.sec.into(), 2, 3);
+                    out.push_str(&format!("{:0w$}", tm.sec));
+                }
+                Key::Ff(n) => {
+                    let n = usize::from(n);
+                    let value = tm.fsec / 10_i32.pow(6 - n as u32);
+                    out.push_str(&format!("{value:0n$}"));
+                }
+                Key::Ms => out.push_str(&format!("{:03}", tm.fsec / 1000)),
+                Key::Us => out.push_str(&format!("{:06}", tm.fsec)),
+                Key::Ssss => {
+                    let secs = tm.hour * 3600 + i64::from(tm.min) * 60 + i64::from(tm.sec);
+                    out.push_str(&secs.to_string());
+                }
+                Key::Mm => {
+                    let w = width(fill, tm.mon.into(), 2, 3);
+                    out.push_str(&format!("{:0w$}", tm.mon));
+                }
+                Key::Ddd => {
+                    let w = width(fill, 0, 3, 3);
+                    out.push_str(&format!("{:0w$}", tm.yday));
+                }
+                Key::Dd => {
+                    let w = width(fill, 0, 2, 2);
+                    out.push_str(&format!("{:0w$}", tm.mday));
+                }
+                Key::Ww => {
+                    let w = width(fill, 0, 2, 2);
+                    out.push_str(&format!("{:0w$}", (tm.yday.wrapping_sub(1)) / 7 + 1));
+                }
+                Key::W => out.push_str(&((tm.mday.wrapping_sub(1)) / 7 + 1).to_string()),
+                Key::Q => {
+                    if tm.mon == 0 {
+                        continue;
+                    }
+                    out.push_str(&((tm.mon - 1) / 3 + 1).to_string());
+                }
+                Key::Cc => {
+                    let cc = tm.year / 100;
+                    if (-99..=99).contains(&cc) {
+                        let w = width(fill, cc.into(), 2, 3);
+                        out.push_str(&format!("{cc:0w$}"));
+                    } else {
+                        out.push_str(&cc.to_string());
+                    }
+                }
+                Key::YComma => {
+                    let thousands = tm.year / 1000;
+                    let rest = tm.year - thousands * 1000;
+                    out.push_str(&format!("{thousands},{rest:03}"));
+                }
+                Key::Yyyy => {
+                    let w = width(fill, tm.year.into(), 4, 5);
+                    out.push_str(&format!("{:0w$}", tm.year));
+                }
+                Key::Yyy => {
+                    let w = width(fill, tm.year.into(), 3, 4);
+                    out.push_str(&format!("{:0w$}", tm.year % 1000));
+                }
+                Key::Yy => {
+                    let w = width(fill, tm.year.into(), 2, 3);
+                    out.push_str(&format!("{:0w$}", tm.year % 100));
+                }
+                Key::Y => out.push_str(&(tm.year % 10).to_string()),
+                Key::Rm { upper } => {
+                    // whole years count as December, or January when negative
+                    if tm.mon == 0 && tm.year == 0 {
+                        continue;
+                    }
+                    let index = match tm.mon {
+                        0 if tm.year >= 0 => 0,
+                        0 => 11,
+                        mon if mon < 0 => (-(mon + 1)) as usize,
+                        mon => (12 - mon) as usize,
+                    };
+                    let numeral = if upper {
+                        RM_MONTHS[index].to_owned()
+                    } else {
+                        RM_MONTHS[index].to_ascii_lowercase()
+                    };
+                    if fill {
+                        out.push_str(&numeral);
+                    } else {
+                        out.push_str(&format!("{numeral:<4}"));
+                    }
+                    continue;
+                }
+                Key::Fx => continue,
+                Key::Invalid => {
+                    return Err("invalid format specification for an interval value".into());
+                }
+                Key::DateOnly => {
+                    return Err(format!(
+                        "format pattern \"{pattern}\" is not supported for intervals"
+                    )
+                    .into());
+                }
+            }
+            push_ordinal(&mut out, start, ordinal);
+        }
+        Ok(Some(out))
+    }
+
+    /// Parse `s` written by [`Interval::format`] with the same template, so
+    /// `Interval::parse_with("27:03:04", "HH24:MI:SS")` is `27:03:04`.
+    ///
+    /// Every pattern reads a number with an optional sign, which applies to
+    /// its field only, as `format` writes them: `-01:-30` is `-01:30:00`
+    /// with `HH24:MI`, but `-01:30` is `-00:30:00`. A pattern reads all the
+    /// digits there are when it has `FM` or is followed by something other
+    /// than another pattern, and otherwise as many as `format` pads it to, so
+    /// `HH24MISS` reads `270304`; a negative number, whose padding includes
+    /// the sign, may have fewer. `TH` skips an ordinal suffix.
+    /// Separators and spaces in the template match any one separator or
+    /// space in `s`, or none; other text must match exactly.
+    ///
+    /// The fields are `YYYY` or `Y,YYY`, `MM`, `DD`, `HH24`, `HH`/`HH12`
+    /// with `AM`/`PM`, `MI`, `SS`, `SSSS` for the seconds of the whole time,
+    /// and the most precise of `MS`, `US` and `FF1`–`FF6` for the fraction.
+    /// The other patterns, which lose information in `format`, fail, and so
+    /// does a field given twice with different values.
+    pub fn parse_with(s: &str, template: &str) -> Result<Self, BoxDynError> {
+        let nodes = parse_template(template);
+        let mismatch = || -> BoxDynError {
+            format!("\"{s}\" does not match the template \"{template}\"").into()
+        };
+
+        let mut fields = ParsedFields::default();
+        let mut rest = s;
+        for (i, node) in nodes.iter().enumerate() {
+            match *node {
+                Node::Char(c) => rest = rest.strip_prefix(c).ok_or_else(mismatch)?,
+                Node::Separator(_) => {
+                    if let Some(c) = rest.chars().next()
+                        && (is_separator_char(c) || c.is_whitespace())
+                    {
+                        rest = &rest[c.len_utf8()..];
+                    }
+                }
+                Node::Action {
+                    key,
+                    pattern,
+                    fill,
+                    ordinal,
+                } => {
+                    let next_is_action = matches!(nodes.get(i + 1), Some(Node::Action { .. }));
+                    let digits = if fill || !next_is_action {
+                        None
+                    } else {
+                        fixed_digits(key)
+                    };
+                    rest = fields.read(key, pattern, rest, digits)?;
+                    if ordinal.is_some() {
+                        let suffix = rest.get(..2).ok_or_else(mismatch)?;
+                        if !["st", "nd", "rd", "th"]
+                            .iter()
+                            .any(|o| suffix.eq_ignore_ascii_case(o))
+                        {
+                            return Err(mismatch());
+                        }
+                        rest = &rest[2..];
+                    }
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Err(mismatch());
+        }
+        fields.into_interval()
+    }
+}
+
+/// The fields [`Interval::parse_with`] has read so far.
+#[derive(Default)]
+struct ParsedFields {
+    year: Option<i64>,
+    mon: Option<i64>,
+    mday: Option<i64>,
+    hour24: Option<i64>,
+    hour12: Option<i64>,
+    pm: Option<bool>,
+    min: Option<i64>,
+    sec: Option<i64>,
+    ssss: Option<i64>,
+    /// the fraction in microseconds and the digits it was given with
+    fraction: Option<(i64, u8)>,
+}
+
+/// Store `value` in `field`, failing if it already holds another value.
+fn set(field: &mut Option<i64>, value: i64, pattern: &str) -> Result<(), BoxDynError> {
+    match field {
+        Some(old) if *old != value => {
+            Err(format!("conflicting values for \"{pattern}\" field in formatting string").into())
+        }
+        _ => {
+            *field = Some(value);
+            Ok(())
+        }
+    }
+}
+
+/// Read an optionally signed number, either `digits` digits or all there are.
+/// The padding of a negative number includes its sign, so it may have fewer.
+fn read_number<'a>(
+    s: &'a str,
+    pattern: &str,
+    digits: Option<usize>,
+) -> Result<(i64, &'a str), BoxDynError> {
+    if s.is_empty() {
+        return Err(format!("source string too short for \"{pattern}\" formatting field").into());
+    }
+    let invalid = || -> BoxDynError {
+        let shown: String = s
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .take(8)
+            .collect();
+        format!("invalid value \"{shown}\" for \"{pattern}\"").into()
+    };
+    let (negative, unsigned) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let available = unsigned.bytes().take_while(u8::is_ascii_digit).count();
+    let len = match digits {
+        Some(n) if negative && available > 0 => available.min(n),
+        Some(n) if available < n => return Err(invalid()),
+        Some(n) => n,
+        None if available == 0 => return Err(invalid()),
+        None => available,
+    };
+    let magnitude: i64 = unsigned[..len].parse().map_err(|_| invalid())?;
+    Ok((
+        if negative { -magnitude } else { magnitude },
+        &unsigned[len..],
+    ))
+}
+
+impl ParsedFields {
+    fn read<'a>(
+        &mut self,
+        key: Key,
+        pattern: &str,
+        s: &'a str,
+        digits: Option<usize>,
+    ) -> Result<&'a str, BoxDynError> {
+        if let Key::Meridiem { upper, dots } = key {
+            let (am, pm) = match (upper, dots) {
+                (true, true) => ("A.M.", "P.M."),
+                (true, false) => ("AM", "PM"),
+                (false, true) => ("a.m.", "p.m."),
+                (false, false) => ("am", "pm"),
+            };
+            let (is_pm, rest) = if let Some(rest) = s.strip_prefix(am) {
+                (false, rest)
+            } else if let Some(rest) = s.strip_prefix(pm) {
+                (true, rest)
+            } else {
+                return Err(format!("invalid value for \"{pattern}\"").into());
+            };
+            if self.pm.is_some_and(|old| old != is_pm) {
+                return Err(format!(
+                    "conflicting values for \"{pattern}\" field in formatting string"
+                )
+                .into());
+            }
+            self.pm = Some(is_pm);
+            return Ok(rest);
+        }
+        if key == Key::Fx {
+            return Ok(s);
+        }
+        if key == Key::YComma {
+            let (thousands, rest) = read_number(s, pattern, None)?;
+            let rest = rest
+                .strip_prefix(',')
+                .ok_or_else(|| format!("invalid value for \"{pattern}\""))?;
+            let (units, rest) = read_number(rest, pattern, digits)?;
+            let year = thousands
+                .checked_mul(1000)
+                .and_then(|y| y.checked_add(units))
+                .ok_or(OUT_OF_RANGE)?;
+            set(&mut self.year, year, pattern)?;
+            return Ok(rest);
+        }
+
+        let field = match key {
+            Key::Yyyy => &mut self.year,
+            Key::Mm => &mut self.mon,
+            Key::Dd => &mut self.mday,
+            Key::Hh24 => &mut self.hour24,
+            Key::Hh => &mut self.hour12,
+            Key::Mi => &mut self.min,
+            Key::Ss => &mut self.sec,
+            Key::Ssss => &mut self.ssss,
+            Key::Ms | Key::Us | Key::Ff(_) => {
+                let (value, rest) = read_number(s, pattern, digits)?;
+                let precision = match key {
+                    Key::Ms => 3,
+                    Key::Us => 6,
+                    Key::Ff(n) => n,
+                    _ => unreachable!(),
+                };
+                let micros = value
+                    .checked_mul(10_i64.pow(6 - u32::from(precision)))
+                    .ok_or(OUT_OF_RANGE)?;
+                match self.fraction {
+                    Some((_, old)) if old >= precision => {}
+                    _ => self.fraction = Some((micros, precision)),
+                }
+                return Ok(rest);
+            }
+            Key::Invalid => {
+                return Err("invalid format specification for an interval value".into());
+            }
+            _ => {
+                return Err(format!(
+                    "format pattern \"{pattern}\" is not supported for parsing an interval"
+                )
+                .into());
+            }
+        };
+        let (value, rest) = read_number(s, pattern, digits)?;
+        set(field, value, pattern)?;
+        Ok(rest)
+    }
+
+    /// `itm2interval`, checking every step for overflow.
+    fn into_interval(self) -> Result<Interval, BoxDynError> {
+        let hour12 = self.hour12.map(|hour| match self.pm {
+            Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+            None => hour,
+        });
+        let hour = match (self.hour24, hour12) {
+            (Some(h24), Some(h12)) if h24 != h12 => {
+                return Err("conflicting values for \"HH\" field in formatting string".into());
+            }
+            (h24, h12) => h24.or(h12).unwrap_or(0),
+        };
+
+        let fraction = self.fraction.map_or(0, |(micros, _)| micros);
+        let overflow = || -> BoxDynError { OUT_OF_RANGE.into() };
+        let mut time = hour
+            .checked_mul(USECS_PER_HOUR)
+            .and_then(|t| t.checked_add(self.min.unwrap_or(0).checked_mul(USECS_PER_MINUTE)?))
+            .and_then(|t| t.checked_add(self.sec.unwrap_or(0).checked_mul(USECS_PER_SEC)?))
+            .ok_or_else(overflow)?;
+        if let Some(ssss) = self.ssss {
+            let ssss = ssss.checked_mul(USECS_PER_SEC).ok_or_else(overflow)?;
+            let has_time = self.hour24.is_some()
+                || self.hour12.is_some()
+                || self.min.is_some()
+                || self.sec.is_some();
+            if has_time && time != ssss {
+                return Err("conflicting values for \"SSSS\" field in formatting string".into());
+            }
+            time = ssss;
+        }
+        let microseconds = time.checked_add(fraction).ok_or_else(overflow)?;
+
+        let months = self
+            .year
+            .unwrap_or(0)
+            .checked_mul(MONTHS_PER_YEAR)
+            .and_then(|m| m.checked_add(self.mon.unwrap_or(0)))
+            .and_then(|m| i32::try_from(m).ok())
+            .ok_or_else(overflow)?;
+        let days = i32::try_from(self.mday.unwrap_or(0)).map_err(|_| overflow())?;
+        let interval = Interval {
+            months,
+            days,
+            microseconds,
+        };
+        if !interval.is_finite() {
+            return Err(overflow());
+        }
+        Ok(interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interval;
+
+    fn format(interval: &str, template: &str) -> String {
+        let interval: Interval = interval.parse().unwrap();
+        interval.format(template).unwrap().unwrap()
+    }
+
+    fn parse_with(s: &str, template: &str) -> Interval {
+        Interval::parse_with(s, template).unwrap()
+    }
+
+    #[test]
+    fn format_matches_to_char() {
+        // `to_char(interval, text)` on PostgreSQL 15
+        for (interval, template, expected) in [
+            ("27:03:04", "HH24:MI:SS", "27:03:04"),
+            ("3 days 04:05:06", "DDD \"days\" HH:MI", "003 days 04:05"),
+            (
+                "1 year 2 mons 3 days 04:05:06.789012",
+                "YYYY-MM-DD HH24:MI:SS.US",
+                "0001-02-03 04:05:06.789012",
+            ),
+            (
+                "1 year 2 mons 3 days 04:05:06.789012",
+                "FMYYYY FMMM FMDD FMHH24 FMMI FMSS",
+                "1 2 3 4 5 6",
+            ),
+            (
+                "-1 year -2 mons -3 days -04:05:06.5",
+                "YYYY MM DD HH24 MI SS MS",
+                "-0001 -02 -3 -04 -05 -06 -500",
+            ),
+            ("13:00:00", "HH12 AM a.m. P.M. pm", "01 PM p.m. P.M. pm"),
+            ("00:00:00", "HH HH12 HH24 AM", "12 12 00 AM"),
+            ("-1 days 25:00:00", "DD HH24 HH AM", "-1 25 01 AM"),
+            (
+                "00:00:01.123456",
+                "FF1 FF2 FF3 FF4 FF5 FF6 MS US",
+                "1 12 123 1234 12345 123456 123 123456",
+            ),
+            (
+                "-00:00:01.123456",
+                "SS FF1 FF3 FF6 MS US",
+                "-01 -1 -123 -123456 -123 -123456",
+            ),
+            ("01:02:03", "SSSS SSSSS", "3723 3723"),
+            (
+                "1 day 01:02:03",
+                "DDDth DDth HH24TH MIth",
+                "001st 01st 01ST 02nd",
+            ),
+            ("21 days 12:00:00", "DDth DDTH HH12th", "21st 21ST 12th"),
+            (
+                "11 days 12 hours 13 minutes",
+                "DDth HH24th MIth",
+                "11th 12th 13th",
+            ),
+            ("5 mons", "MM Q RM rm FMRM|", "05 2 V    v    V|"),
+            ("-5 mons", "MM Q RM rm", "-05 -1 VIII viii"),
+            ("2 years", "RM|YYYY", "XII |0002"),
+            ("-2 years", "RM|YYYY", "I   |-0002"),
+            (
+                "1234 years",
+                "Y,YYY YYYY YYY YY Y CC",
+                "1,234 1234 234 34 4 12",
+            ),
+            (
+                "-1234 years",
+                "Y,YYY YYYY YYY YY Y CC",
+                "-1,-234 -1234 -234 -34 -4 -12",
+            ),
+            ("45 days", "W WW DDD", "7 07 045"),
+            ("1 day", "\"HH24\" \\\"HH24\\\" HH24", "HH24 \"00\" 00"),
+            ("1 day", "FXHH24", "00"),
+        ] {
+            assert_eq!(
+                format(interval, template),
+                expected,
+                "{interval} {template}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_null_and_errors() {
+        let day: Interval = "1 day".parse().unwrap();
+        assert_eq!(day.format("").unwrap(), None);
+        assert_eq!(Interval::INFINITY.format("HH24").unwrap(), None);
+        assert_eq!(Interval::NEG_INFINITY.format("HH24").unwrap(), None);
+        for template in ["Month", "DAY", "TZ", "D", "AD"] {
+            assert_eq!(
+                day.format(template).unwrap_err().to_string(),
+                "invalid format specification for an interval value"
+            );
+        }
+        assert_eq!(
+            day.format("IYYY").unwrap_err().to_string(),
+            "format pattern \"IYYY\" is not supported for intervals"
+        );
+    }
+
+    #[test]
+    fn parse_with_reads_fields() {
+        for (s, template, expected) in [
+            ("27:03:04", "HH24:MI:SS", "27:03:04"),
+            ("270304", "HH24MISS", "27:03:04"),
+            ("-01:-30", "HH24:MI", "-01:30:00"),
+            ("-01:30", "HH24:MI", "-00:30:00"),
+            ("01:30 PM", "HH12:MI AM", "13:30:00"),
+            ("12:00 AM", "HH:MI PM", "00:00:00"),
+            ("3 days", "DD \"days\"", "3 days"),
+            ("1,234 5", "Y,YYY MM", "1234 years 5 mons"),
+            ("5400", "SSSS", "01:30:00"),
+            ("01:30 5400", "HH24:MI SSSS", "01:30:00"),
+            ("1.5 500000", "SS.FF1 US", "00:00:01.5"),
+            ("21st 3rd", "DDth HH24th", "21 days 03:00:00"),
+            ("", "", "00:00:00"),
+        ] {
+            assert_eq!(
+                parse_with(s, template).to_string(),
+                expected,
+                "{s} {template}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_with_round_trips_format() {
+        for interval in [
+            "1 year 2 mons 3 days 04:05:06.789012",
+            "-1 year -2 mons -3 days -04:05:06.5",
+            "-178956970 years -8 mons -2147483648 days -2562047788:00:54.775807",
+            "11 mons -20 days +100:00:00.000001",
+            "00:00:00",
+        ] {
+            let interval: Interval = interval.parse().unwrap();
+            for template in [
+                "YYYY MM DD HH24:MI:SS.US",
+                "FMYYYY-FMMM-FMDD FMHH24:FMMI:FMSS.FF6",
+                "Y,YYY \"y\" MM \"m\" DD \"d\" HH24 MI SS US",
+                "YYYY/MM/DD SSSS.FF6",
+            ] {
+                let s = interval.format(template).unwrap().unwrap();
+                let back = Interval::parse_with(&s, template).unwrap();
+                assert!(back.is_identical(&interval), "{s} {template}");
+            }
+        }
+    }
+
+    #[test]
+    fn parse_with_errors() {
+        for (s, template, error) in [
+            ("9223372036854775807", "MS", "interval out of range"),
+            ("9223372036854775807", "HH24", "interval out of range"),
+            (
+                "3 days",
+                "DD days",
+                "invalid format specification for an interval value",
+            ),
+            (
+                "01:02",
+                "HH24:MI:SS",
+                "source string too short for \"SS\" formatting field",
+            ),
+            (
+                "1:2:3",
+                "HH24:MI",
+                "\"1:2:3\" does not match the template \"HH24:MI\"",
+            ),
+            ("xx", "HH24", "invalid value \"xx\" for \"HH24\""),
+            (
+                "2 3",
+                "DD DD",
+                "conflicting values for \"DD\" field in formatting string",
+            ),
+            (
+                "01:31 5400",
+                "HH24:MI SSSS",
+                "conflicting values for \"SSSS\" field in formatting string",
+            ),
+            (
+                "5",
+                "Q",
+                "format pattern \"Q\" is not supported for parsing an interval",
+            ),
+        ] {
+            assert_eq!(
+                Interval::parse_with(s, template).unwrap_err().to_string(),
+                error,
+                "{s} {template}"
+            );
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! [`IntervalArithmetic`] for the `time` crate's date and time types.
+
+use std::ops::{Add, Sub};
+
+use sqlx::error::BoxDynError;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime};
+
+use crate::{
+    Interval, IntervalAnchor, IntervalArithmetic, MONTHS_PER_YEAR,
+    datetime::{self, TIMESTAMP_OUT_OF_RANGE, Tm},
+};
+
+/// Add months to `date`, clamping the day to the end of a shorter month.
+fn add_months(date: Date, months: i32) -> Result<Date, BoxDynError> {
+    if months == 0 {
+        return Ok(date);
+    }
+    let month0 = i64::from(date.year()) * MONTHS_PER_YEAR + i64::from(u8::from(date.month())) - 1
+        + i64::from(months);
+    let year =
+        i32::try_from(month0.div_euclid(MONTHS_PER_YEAR)).map_err(|_| TIMESTAMP_OUT_OF_RANGE)?;
+    let month = Month::try_from(month0.rem_euclid(MONTHS_PER_YEAR) as u8 + 1)?;
+    let day = date.day().min(month.length(year));
+    Ok(Date::from_calendar_date(year, month, day).map_err(|_| TIMESTAMP_OUT_OF_RANGE)?)
+}
+
+fn add_days(date: Date, days: i32) -> Result<Date, BoxDynError> {
+    Ok(date
+        .checked_add(Duration::days(days.into()))
+        .ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+}
+
+/// `timestamp2tm`
+fn tm(local: &PrimitiveDateTime) -> Tm {
+    Tm {
+        year: local.year(),
+        mon: u8::from(local.month()).into(),
+        mday: local.day().into(),
+        hour: local.hour().into(),
+        min: local.minute().into(),
+        sec: local.second().into(),
+        usec: local.microsecond() as i32,
+    }
+}
+
+impl IntervalArithmetic for Date {
+    type Output = PrimitiveDateTime;
+
+    /// `date + interval`, which the server computes on the date's midnight.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<PrimitiveDateTime, BoxDynError> {
+        self.midnight().checked_add_interval(interval)
+    }
+}
+
+impl IntervalArithmetic for PrimitiveDateTime {
+    type Output = PrimitiveDateTime;
+
+    /// `timestamp_pl_interval`
+    fn checked_add_interval(&self, interval: &Interval) -> Result<PrimitiveDateTime, BoxDynError> {
+        datetime::check_finite(interval)?;
+        let date = add_days(add_months(self.date(), interval.months)?, interval.days)?;
+        Ok(self
+            .replace_date(date)
+            .checked_add(Duration::microseconds(interval.microseconds))
+            .ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+    }
+}
+
+impl IntervalArithmetic for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    /// `timestamptz_pl_interval` in a session whose time zone is the value's
+    /// fixed UTC offset: months and days are added to the local date.
+    fn checked_add_interval(&self, interval: &Interval) -> Result<OffsetDateTime, BoxDynError> {
+        datetime::check_finite(interval)?;
+        let date = add_days(add_months(self.date(), interval.months)?, interval.days)?;
+        Ok(self
+            .replace_date(date)
+            .checked_add(Duration::microseconds(interval.microseconds))
+            .ok_or(TIMESTAMP_OUT_OF_RANGE)?)
+    }
+}
+
+impl IntervalAnchor for PrimitiveDateTime {
+    type Duration = Duration;
+
+    fn elapsed_since(&self, earlier: &Self) -> Duration {
+        *self - *earlier
+    }
+
+    /// `timestamp_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::age(tm(self), tm(earlier), self < earlier)
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::difference(self.elapsed_since(earlier).whole_microseconds())
+    }
+}
+
+impl IntervalAnchor for OffsetDateTime {
+    type Duration = Duration;
+
+    fn elapsed_since(&self, earlier: &Self) -> Duration {
+        *self - *earlier
+    }
+
+    /// `timestamptz_age`
+    fn age_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        let local =
+            |value: &OffsetDateTime| tm(&PrimitiveDateTime::new(value.date(), value.time()));
+        datetime::age(
+            local(self),
+            local(&earlier.to_offset(self.offset())),
+            self < earlier,
+        )
+    }
+
+    /// `timestamp_mi`
+    fn interval_since(&self, earlier: &Self) -> Result<Interval, BoxDynError> {
+        datetime::difference(self.elapsed_since(earlier).whole_microseconds())
+    }
+}
+
+impl Add<Interval> for Date {
+    type Output = PrimitiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> PrimitiveDateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for Date {
+    type Output = PrimitiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> PrimitiveDateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add<Interval> for PrimitiveDateTime {
+    type Output = PrimitiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> PrimitiveDateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for PrimitiveDateTime {
+    type Output = PrimitiveDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> PrimitiveDateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Add<Interval> for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_add_interval`].
+    fn add(self, rhs: Interval) -> OffsetDateTime {
+        self.checked_add_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sub<Interval> for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    /// # Panics
+    ///
+    /// If the result is out of range; see [`IntervalArithmetic::checked_sub_interval`].
+    fn sub(self, rhs: Interval) -> OffsetDateTime {
+        self.checked_sub_interval(&rhs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    use crate::{
+        Interval, IntervalArithmetic,
+        datetime::{AGE_CASES, Civil, DURATION_CASES},
+    };
+
+    fn timestamp((year, month, day, hour, min, sec, micro): Civil) -> PrimitiveDateTime {
+        Date::from_calendar_date(year, Month::try_from(month).unwrap(), day)
+            .and_then(|date| date.with_hms_micro(hour, min, sec, micro))
+            .unwrap()
+    }
+
+    #[test]
+    fn age_and_difference() {
+        for &(later, earlier, age, difference, sum) in AGE_CASES {
+            let (later, earlier) = (timestamp(later), timestamp(earlier));
+            let interval = Interval::age(&later, &earlier).unwrap();
+            assert_eq!(interval.to_string(), age, "age({later}, {earlier})");
+            let elapsed = Interval::difference(&later, &earlier).unwrap();
+            assert_eq!(elapsed.to_string(), difference, "{later} - {earlier}");
+            let sum = timestamp(sum);
+            assert_eq!(earlier.checked_add_interval(&interval).unwrap(), sum);
+        }
+    }
+
+    #[test]
+    fn to_duration() {
+        for &(anchor, input, from, until) in DURATION_CASES {
+            let (anchor, interval) = (timestamp(anchor), input.parse::<Interval>().unwrap());
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration.whole_microseconds(),
+                from.into(),
+                "{input} from {anchor}"
+            );
+            // a negative one has no `std::time::Duration`
+            assert_eq!(std::time::Duration::try_from(duration).is_ok(), from >= 0);
+            let duration = interval.to_duration_until(&anchor).unwrap();
+            assert_eq!(
+                duration.whole_microseconds(),
+                until.into(),
+                "{input} until {anchor}"
+            );
+            let anchor = anchor.assume_offset(UtcOffset::from_hms(-5, 0, 0).unwrap());
+            let duration = interval.to_duration_from(&anchor).unwrap();
+            assert_eq!(
+                duration.whole_microseconds(),
+                from.into(),
+                "{input} from {anchor}"
+            );
+        }
+    }
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap()
+    }
+
+    #[test]
+    fn add_months_clamps_to_month_end() {
+        // `date + interval` on the server
+        for (start, input, expected) in [
+            (date(2024, 1, 31), "1 mon", date(2024, 2, 29)),
+            (date(2023, 1, 31), "1 mon", date(2023, 2, 28)),
+            (date(2024, 2, 29), "1 year", date(2025, 2, 28)),
+            (date(2024, 2, 29), "-1 year", date(2023, 2, 28)),
+            (date(2024, 2, 29), "4 years", date(2028, 2, 29)),
+            (date(2024, 12, 31), "2 mons", date(2025, 2, 28)),
+            (date(2024, 3, 31), "-13 mons", date(2023, 2, 28)),
+            (date(2024, 1, 15), "-1 mon", date(2023, 12, 15)),
+            (date(2024, 5, 31), "-25 mons", date(2022, 4, 30)),
+            (date(2024, 8, 31), "-6 mons", date(2024, 2, 29)),
+            // months first, then days
+            (date(2024, 1, 31), "1 mon 1 day", date(2024, 3, 1)),
+            (date(2024, 3, 31), "-1 mon -1 day", date(2024, 2, 28)),
+        ] {
+            let interval = input.parse::<Interval>().unwrap();
+            let result = start.checked_add_interval(&interval).unwrap();
+            assert_eq!(result, expected.midnight(), "{start} + {input}");
+        }
+    }
+
+    #[test]
+    fn offset_date_time_keeps_its_offset() {
+        let tokyo = UtcOffset::from_hms(9, 0, 0).unwrap();
+        let at = |local| timestamp(local).assume_offset(tokyo);
+        // the local date is 2024-01-31, though it is still 2024-01-30 in UTC
+        let start = at((2024, 1, 31, 1, 0, 0, 0));
+        let result = start + "1 mon".parse::<Interval>().unwrap();
+        assert_eq!(result, at((2024, 2, 29, 1, 0, 0, 0)));
+        assert_eq!(result.offset(), tokyo);
+        // a fixed offset has no DST, so a day is always 24 hours
+        let new_york = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        let start = timestamp((2024, 3, 9, 12, 0, 0, 0)).assume_offset(new_york);
+        let day = "1 day".parse::<Interval>().unwrap();
+        assert_eq!((start + day.clone()) - start, time::Duration::hours(24));
+        assert_eq!(
+            day.to_duration_from(&start).unwrap(),
+            time::Duration::hours(24)
+        );
+        let result = start - "1 mon 00:00:00.000001".parse::<Interval>().unwrap();
+        assert_eq!(
+            result,
+            timestamp((2024, 2, 9, 11, 59, 59, 999_999)).assume_offset(new_york)
+        );
+    }
+
+    #[test]
+    fn operators() {
+        let start = timestamp((2024, 1, 31, 12, 0, 0, 0));
+        let interval = |s: &str| s.parse::<Interval>().unwrap();
+        assert_eq!(
+            start + interval("1 mon 1 day"),
+            timestamp((2024, 3, 1, 12, 0, 0, 0))
+        );
+        assert_eq!(
+            start - interval("1 day 02:00:00"),
+            timestamp((2024, 1, 30, 10, 0, 0, 0))
+        );
+        assert_eq!(
+            date(2024, 1, 31) + interval("1 mon"),
+            date(2024, 2, 29).midnight()
+        );
+        assert_eq!(
+            date(2024, 3, 1) - interval("1 sec"),
+            timestamp((2024, 2, 29, 23, 59, 59, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_out_of_range() {
+        let _ = Date::MAX + "1 day".parse::<Interval>().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn sub_panics_out_of_range() {
+        let _ = PrimitiveDateTime::MIN - "1 mon".parse::<Interval>().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn offset_date_time_add_panics_out_of_range() {
+        let _ = OffsetDateTime::new_utc(Date::MAX, time::Time::MIDNIGHT) + Interval::new(12, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn add_panics_on_infinity() {
+        let _ = date(2024, 1, 1) + Interval::INFINITY;
+    }
+}